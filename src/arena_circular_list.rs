@@ -0,0 +1,501 @@
+use std::marker::PhantomData;
+
+/// A circular doubly linked list whose nodes live in a single [`Vec`].
+///
+/// Nodes link to each other by index instead of by pointer, which keeps them close together
+/// in memory. Removed slots go on a free list and get reused by later insertions.
+///
+/// Every insertion returns a [`NodeId`], a handle that keeps referring to the same element
+/// no matter what else is inserted or removed, until that element itself is removed.
+///
+/// # Usage
+/// ```
+/// # use garlic::arena_circular_list::*;
+/// let mut acl = ArenaCircularList::new();
+/// let mike = acl.push("Mike");
+/// let hank = acl.push("Hank");
+/// acl.push("Gus");
+///
+/// assert_eq!(acl.remove(hank), Some("Hank"));
+/// acl.insert_after(mike, "Walter");
+/// assert_eq!(acl.get(mike), Some(&"Mike"));
+/// assert_eq!(acl.get(hank), None);
+///
+/// let names: Vec<_> = acl.iter().map_copied().take(4).collect();
+/// assert_eq!(names, ["Mike", "Walter", "Gus", "Mike"]);
+/// ```
+#[derive(Clone)]
+pub struct ArenaCircularList<T> {
+    slots: Vec<Slot<T>>,
+    free: Vec<usize>,
+    head: Option<usize>,
+    len: usize,
+}
+
+#[derive(Clone)]
+struct Slot<T> {
+    value: Option<T>,
+    next: usize,
+    prev: usize,
+    /// Bumped whenever the slot is freed. 64 bits can't wrap around in practice,
+    /// so a stale [`NodeId`] never becomes valid again.
+    generation: u64,
+}
+
+/// A handle to an element of an [`ArenaCircularList`].
+///
+/// Stays valid until the element it refers to is removed,
+/// after which every lookup with it returns `None`, even if its slot gets reused.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct NodeId {
+    index: usize,
+    generation: u64,
+}
+
+impl<T> Default for ArenaCircularList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> ArenaCircularList<T> {
+    pub fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            free: Vec::new(),
+            head: None,
+            len: 0,
+        }
+    }
+
+    /// Creates an empty list with room for `capacity` elements before reallocating.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            slots: Vec::with_capacity(capacity),
+            ..Self::new()
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    /// Pushes an element after the tail.
+    pub fn push(&mut self, value: T) -> NodeId {
+        match self.head {
+            Some(head) => self.link_after(self.slots[head].prev, value),
+            None => self.push_first(value),
+        }
+    }
+
+    /// Pushes an element in front of the head, making it the new head.
+    pub fn push_front(&mut self, value: T) -> NodeId {
+        let id = self.push(value);
+        self.head = Some(id.index);
+        id
+    }
+
+    /// Inserts an element right after the one `id` refers to.
+    ///
+    /// Returns `None`, dropping `value`, if `id` is no longer valid.
+    pub fn insert_after(&mut self, id: NodeId, value: T) -> Option<NodeId> {
+        self.check(id)?;
+        Some(self.link_after(id.index, value))
+    }
+
+    /// Removes the element `id` refers to and returns it.
+    ///
+    /// Returns `None` if `id` is no longer valid.
+    pub fn remove(&mut self, id: NodeId) -> Option<T> {
+        self.check(id)?;
+        Some(self.unlink(id.index))
+    }
+
+    /// Removes the head element and returns it, or `None` if the list is empty.
+    pub fn pop_front(&mut self) -> Option<T> {
+        let head = self.head?;
+        Some(self.unlink(head))
+    }
+
+    /// Removes the tail element and returns it, or `None` if the list is empty.
+    pub fn pop_back(&mut self) -> Option<T> {
+        let head = self.head?;
+        Some(self.unlink(self.slots[head].prev))
+    }
+
+    /// Removes all elements, invalidating every [`NodeId`].
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    pub fn get(&self, id: NodeId) -> Option<&T> {
+        self.check(id)?;
+        self.slots[id.index].value.as_ref()
+    }
+
+    pub fn get_mut(&mut self, id: NodeId) -> Option<&mut T> {
+        self.check(id)?;
+        self.slots[id.index].value.as_mut()
+    }
+
+    /// Whether `id` still refers to an element of this list.
+    pub fn contains(&self, id: NodeId) -> bool {
+        self.check(id).is_some()
+    }
+
+    /// Handle to the head element, or `None` if the list is empty.
+    pub fn head(&self) -> Option<NodeId> {
+        self.head.map(|index| self.id(index))
+    }
+
+    /// Handle to the tail element, or `None` if the list is empty.
+    pub fn tail(&self) -> Option<NodeId> {
+        self.head.map(|index| self.id(self.slots[index].prev))
+    }
+
+    /// Handle to the element after the one `id` refers to.
+    pub fn next(&self, id: NodeId) -> Option<NodeId> {
+        self.check(id)?;
+        Some(self.id(self.slots[id.index].next))
+    }
+
+    /// Handle to the element before the one `id` refers to.
+    pub fn prev(&self, id: NodeId) -> Option<NodeId> {
+        self.check(id)?;
+        Some(self.id(self.slots[id.index].prev))
+    }
+
+    /// Moves the head `n` nodes forward along the circle,
+    /// so the element at index `n` becomes the new head.
+    pub fn rotate_forward(&mut self, n: usize) {
+        let Some(mut head) = self.head else {
+            return;
+        };
+        for _ in 0..n % self.len {
+            head = self.slots[head].next;
+        }
+        self.head = Some(head);
+    }
+
+    /// Makes the element `id` refers to the new head.
+    ///
+    /// Returns `false` and leaves the list untouched if `id` is no longer valid.
+    ///
+    /// ```
+    /// # use garlic::arena_circular_list::*;
+    /// let mut acl: ArenaCircularList<_> = (1..=3).collect();
+    /// let four = acl.push(4);
+    /// acl.push(5);
+    /// assert!(acl.rotate_to(four));
+    /// assert_eq!(format!("{acl:?}"), "[4, 5, 1, 2, 3]");
+    /// ```
+    pub fn rotate_to(&mut self, id: NodeId) -> bool {
+        if self.check(id).is_none() {
+            return false;
+        }
+        self.head = Some(id.index);
+        true
+    }
+
+    fn id(&self, index: usize) -> NodeId {
+        NodeId {
+            index,
+            generation: self.slots[index].generation,
+        }
+    }
+
+    fn check(&self, id: NodeId) -> Option<()> {
+        let slot = self.slots.get(id.index)?;
+        (slot.generation == id.generation && slot.value.is_some()).then_some(())
+    }
+
+    /// Takes a slot from the free list, or grows the arena.
+    fn alloc(&mut self, value: T) -> usize {
+        self.len += 1;
+        match self.free.pop() {
+            Some(index) => {
+                self.slots[index].value = Some(value);
+                index
+            }
+            None => {
+                self.slots.push(Slot {
+                    value: Some(value),
+                    next: 0,
+                    prev: 0,
+                    generation: 0,
+                });
+                self.slots.len() - 1
+            }
+        }
+    }
+
+    /// Pushes into an empty list, creating the self-looping node.
+    fn push_first(&mut self, value: T) -> NodeId {
+        let index = self.alloc(value);
+        self.slots[index].next = index;
+        self.slots[index].prev = index;
+        self.head = Some(index);
+        self.id(index)
+    }
+
+    /// Links a new node right after `prev` and returns its handle.
+    ///
+    /// Leaves `head` untouched.
+    fn link_after(&mut self, prev: usize, value: T) -> NodeId {
+        let index = self.alloc(value);
+        let next = self.slots[prev].next;
+        self.slots[index].next = next;
+        self.slots[index].prev = prev;
+        self.slots[prev].next = index;
+        self.slots[next].prev = index;
+        self.id(index)
+    }
+
+    /// Unlinks the node at `index`, frees its slot and returns its value.
+    fn unlink(&mut self, index: usize) -> T {
+        let Slot { next, prev, .. } = self.slots[index];
+        if next == index {
+            self.head = None;
+        } else {
+            self.slots[prev].next = next;
+            self.slots[next].prev = prev;
+            if self.head == Some(index) {
+                self.head = Some(next);
+            }
+        }
+
+        let slot = &mut self.slots[index];
+        slot.generation += 1;
+        self.free.push(index);
+        self.len -= 1;
+        slot.value.take().unwrap()
+    }
+
+    /// Creates an iterator that, by default,
+    /// will never end, unless the list is empty.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            list: self,
+            cursor: self.head,
+            remaining: None,
+        }
+    }
+
+    /// Creates an iterator that, by default,
+    /// will iterate throught the list and stop at the tail element.
+    pub fn iter_once(&self) -> Iter<'_, T> {
+        Iter {
+            remaining: Some(self.len),
+            ..self.iter()
+        }
+    }
+
+    /// Creates an iterator over mutable references that stops at the tail element.
+    ///
+    /// ```
+    /// # use garlic::arena_circular_list::*;
+    /// let mut acl: ArenaCircularList<_> = (1..=3).collect();
+    /// let two = acl.head().and_then(|id| acl.next(id)).unwrap();
+    /// acl.remove(two);
+    /// acl.push_front(0);
+    /// for (i, x) in acl.iter_mut().enumerate() {
+    ///     *x += i * 10;
+    /// }
+    /// assert_eq!(format!("{acl:?}"), "[0, 11, 23]");
+    /// ```
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            slots: self.slots.as_mut_ptr(),
+            cursor: self.head.unwrap_or(0),
+            remaining: self.len,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Creates an iterator over the handles of every element, from the head to the tail.
+    ///
+    /// ```
+    /// # use garlic::arena_circular_list::*;
+    /// let mut acl: ArenaCircularList<_> = (1..=5).collect();
+    /// let odd: Vec<_> = acl.ids().filter(|&id| acl[id] % 2 == 1).collect();
+    /// for id in odd {
+    ///     acl.remove(id);
+    /// }
+    /// assert_eq!(format!("{acl:?}"), "[2, 4]");
+    /// ```
+    pub fn ids(&self) -> impl Iterator<Item = NodeId> + '_ {
+        let mut cursor = self.head;
+        std::iter::from_fn(move || {
+            let index = cursor?;
+            let next = self.slots[index].next;
+            cursor = (Some(next) != self.head).then_some(next);
+            Some(self.id(index))
+        })
+    }
+}
+
+/// # Panics
+/// Panics if `id` is no longer valid.
+impl<T> std::ops::Index<NodeId> for ArenaCircularList<T> {
+    type Output = T;
+
+    fn index(&self, id: NodeId) -> &T {
+        self.get(id).expect("node was removed from the list")
+    }
+}
+
+/// # Panics
+/// Panics if `id` is no longer valid.
+impl<T> std::ops::IndexMut<NodeId> for ArenaCircularList<T> {
+    fn index_mut(&mut self, id: NodeId) -> &mut T {
+        self.get_mut(id).expect("node was removed from the list")
+    }
+}
+
+#[derive(Clone)]
+pub struct Iter<'a, T> {
+    list: &'a ArenaCircularList<T>,
+    cursor: Option<usize>,
+    remaining: Option<usize>,
+}
+
+impl<'a, T> Iter<'a, T> {
+    /// Stops the iterator at the tail element.
+    pub fn once(mut self) -> Self {
+        let Some(cursor) = self.cursor else {
+            return self;
+        };
+
+        let mut remaining = 1;
+        let mut index = cursor;
+        while Some(self.list.slots[index].next) != self.list.head {
+            index = self.list.slots[index].next;
+            remaining += 1;
+        }
+        self.remaining = Some(remaining);
+        self
+    }
+
+    /// Copies each value.
+    ///
+    /// Equivelent to `acl.copied()`
+    pub fn map_copied(self) -> impl Iterator<Item = T> + 'a
+    where
+        T: Copy,
+    {
+        self.copied()
+    }
+
+    /// Clones each value.
+    ///
+    /// Equivelent to `acl.cloned()`
+    pub fn map_cloned(self) -> impl Iterator<Item = T> + 'a
+    where
+        T: Clone,
+    {
+        self.cloned()
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let index = self.cursor?;
+        match &mut self.remaining {
+            Some(0) => return None,
+            Some(remaining) => *remaining -= 1,
+            None => {}
+        }
+
+        let slot = &self.list.slots[index];
+        self.cursor = Some(slot.next);
+        slot.value.as_ref()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match (self.cursor, self.remaining) {
+            (None, _) => (0, Some(0)),
+            (Some(_), None) => (usize::MAX, None),
+            (Some(_), Some(remaining)) => (remaining, Some(remaining)),
+        }
+    }
+}
+
+/// Only meaningful after [`Iter::once`].
+/// Calling [`len`](ExactSizeIterator::len) on a never ending iterator panics.
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+/// Walks the `next` indices through a pointer to the slots,
+/// since the borrow checker can't tell the elements it hands out apart.
+pub struct IterMut<'a, T> {
+    slots: *mut Slot<T>,
+    cursor: usize,
+    remaining: usize,
+    _marker: PhantomData<&'a mut T>,
+}
+
+// SAFETY: `IterMut` only hands out `&mut T`, like `std::slice::IterMut`.
+unsafe impl<T: Send> Send for IterMut<'_, T> {}
+unsafe impl<T: Sync> Sync for IterMut<'_, T> {}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+
+        // SAFETY: the list is mutably borrowed for `'a`, and following `next` from the head
+        // visits each of its `len` linked slots exactly once, so no value is handed out twice.
+        unsafe {
+            let slot = self.slots.add(self.cursor);
+            self.cursor = (*slot).next;
+            (*slot).value.as_mut()
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for ArenaCircularList<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        f.debug_list().entries(self.iter_once()).finish()
+    }
+}
+
+impl<T> std::iter::FromIterator<T> for ArenaCircularList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut acl = Self::new();
+        acl.extend(iter);
+        acl
+    }
+}
+
+impl<T> Extend<T> for ArenaCircularList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.push(x);
+        }
+    }
+}
+
+/// Iterates through the list once, like [`ArenaCircularList::iter_once`].
+impl<'a, T> IntoIterator for &'a ArenaCircularList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter_once()
+    }
+}
@@ -0,0 +1,1180 @@
+use crate::circular_linked_list::{is_rotation, least_rotation};
+use std::{
+    cell::{OnceCell, Ref, RefCell, RefMut},
+    cmp::Ordering,
+    collections::{LinkedList, VecDeque},
+    hash::{Hash, Hasher},
+    ops::{Deref, DerefMut},
+    rc::{Rc, Weak},
+};
+
+/// A circular doubly linked list.
+///
+/// Same as [`CircularLinkedList`], every node also links back to the one before it,
+/// but only the head is kept, the tail is found through its backward link,
+/// so [`pop_back`] and [`rotate_backward`] run in O(1)
+/// and the once around iterators are double ended.
+///
+/// The backward links are [`Weak`], so the `Rc` cycle only runs forward
+/// and is broken when the list is dropped.
+///
+/// [`CircularLinkedList`]: crate::circular_linked_list::CircularLinkedList
+/// [`pop_back`]: CircularDoublyLinkedList::pop_back
+/// [`rotate_backward`]: CircularDoublyLinkedList::rotate_backward
+///
+/// # Usage
+/// ```
+/// # use garlic::circular_doubly_linked_list::*;
+/// let mut cdll = CircularDoublyLinkedList::new();
+/// cdll.push("Mike");
+/// cdll.push("Hank");
+/// cdll.push("Gus");
+///
+/// let backwards: Vec<_> = cdll.iter_rev().map(|x| x.borrow().value).take(4).collect();
+/// assert_eq!(backwards, ["Gus", "Hank", "Mike", "Gus"]);
+///
+/// let cdll: CircularDoublyLinkedList<_> = "hello".chars().collect();
+/// let doubled: String = cdll.iter().map_copied().take(cdll.len() * 2).collect();
+/// assert_eq!(doubled, "hellohello");
+/// ```
+pub struct CircularDoublyLinkedList<T> {
+    head: Pointer<T>,
+    len: usize,
+    /// Every node from the head to the tail, collected when values are borrowed,
+    /// so the borrows can't outlive the nodes whatever happens to their links.
+    /// Cleared by every structural change.
+    nodes: OnceCell<Vec<Rcrfn<T>>>,
+}
+
+pub struct Node<T> {
+    pub value: T,
+    next: Link<T>,
+    prev: Weak<RefCell<Node<T>>>,
+}
+
+type Rcrfn<T> = Rc<RefCell<Node<T>>>;
+type Pointer<T> = Option<Rcrfn<T>>;
+
+/// The owning link from a node to the next one.
+///
+/// Drops the chain behind it one node at a time, instead of letting it drop recursively,
+/// so releasing the last reference to a long detached chain can't overflow the stack.
+struct Link<T>(Pointer<T>);
+
+impl<T> Deref for Link<T> {
+    type Target = Pointer<T>;
+
+    fn deref(&self) -> &Pointer<T> {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Link<T> {
+    fn deref_mut(&mut self) -> &mut Pointer<T> {
+        &mut self.0
+    }
+}
+
+impl<T> Drop for Link<T> {
+    fn drop(&mut self) {
+        let mut next = self.0.take();
+        while let Some(node) = next {
+            next = Rc::try_unwrap(node)
+                .ok()
+                .and_then(|cell| cell.into_inner().next.take());
+        }
+    }
+}
+
+/// ```
+/// # use garlic::circular_doubly_linked_list::*;
+/// struct NoDefault;
+/// let cdll = CircularDoublyLinkedList::<NoDefault>::default();
+/// assert!(cdll.is_empty());
+/// ```
+impl<T> Default for CircularDoublyLinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> CircularDoublyLinkedList<T> {
+    pub fn new() -> Self {
+        Self {
+            head: None,
+            len: 0,
+            nodes: OnceCell::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    pub fn push(&mut self, value: T) {
+        let Some(tail) = self.tail() else {
+            return self.push_first(value);
+        };
+        Self::link_after(&tail, value);
+        self.len += 1;
+        self.reshaped();
+    }
+
+    /// Pushes an element in front of the head, making it the new head.
+    pub fn push_front(&mut self, value: T) {
+        let Some(tail) = self.tail() else {
+            return self.push_first(value);
+        };
+        self.head = Some(Self::link_after(&tail, value));
+        self.len += 1;
+        self.reshaped();
+    }
+
+    /// Inserts an element so that it ends up at position `index`.
+    ///
+    /// `index` wraps around the circle, so inserting at `len` is the same as
+    /// inserting at `0`: the new element becomes the head.
+    ///
+    /// ```
+    /// # use garlic::circular_doubly_linked_list::*;
+    /// let mut cdll: CircularDoublyLinkedList<_> = "ace".chars().collect();
+    /// cdll.insert(1, 'b');
+    /// cdll.insert(7, 'd');
+    /// assert_eq!(format!("{cdll:?}"), "['a', 'b', 'c', 'd', 'e']");
+    /// ```
+    pub fn insert(&mut self, index: usize, value: T) {
+        if self.len == 0 {
+            return self.push_first(value);
+        }
+
+        match index % self.len {
+            0 => self.push_front(value),
+            i => {
+                let prev = self.node_at(i - 1).unwrap();
+                Self::link_after(&prev, value);
+                self.len += 1;
+                self.reshaped();
+            }
+        }
+    }
+
+    /// Removes the head element and returns it, or `None` if the list is empty.
+    ///
+    /// # Panics
+    /// Panics if the node is still referenced elsewhere, e.g. by a live [`CdllIter`].
+    pub fn pop_front(&mut self) -> Option<T> {
+        let head = self.head.clone()?;
+        Some(Self::into_value(self.unlink(head)))
+    }
+
+    /// Removes the tail element and returns it, or `None` if the list is empty.
+    ///
+    /// ```
+    /// # use garlic::circular_doubly_linked_list::*;
+    /// let mut cdll = CircularDoublyLinkedList::from([1, 2, 3]);
+    /// assert_eq!(cdll.pop_back(), Some(3));
+    /// assert_eq!(cdll.pop_front(), Some(1));
+    /// assert_eq!(cdll.pop_back(), Some(2));
+    /// assert_eq!(cdll.pop_back(), None);
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if the node is still referenced elsewhere, e.g. by a live [`CdllIter`].
+    pub fn pop_back(&mut self) -> Option<T> {
+        let tail = self.tail()?;
+        Some(Self::into_value(self.unlink(tail)))
+    }
+
+    /// Removes the element at `index` and returns it,
+    /// or `None` if `index` is out of bounds.
+    ///
+    /// Walks from whichever end of the list is closer.
+    ///
+    /// # Panics
+    /// Panics if the node is still referenced elsewhere, e.g. by a live [`CdllIter`].
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        let node = self.node_at(index)?;
+        Some(Self::into_value(self.unlink(node)))
+    }
+
+    /// Keeps the first `len` elements and drops the rest.
+    ///
+    /// Does nothing if `len` is greater than or equal to the list's current length.
+    pub fn truncate(&mut self, len: usize) {
+        while self.len > len {
+            let tail = self.tail().unwrap();
+            self.unlink(tail);
+        }
+    }
+
+    /// Removes all elements.
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Moves the head `n` nodes forward along the circle,
+    /// so the element at index `n` becomes the new head.
+    ///
+    /// Walks whichever way around the circle is shorter,
+    /// no nodes are reallocated or relinked.
+    ///
+    /// ```
+    /// # use garlic::circular_doubly_linked_list::*;
+    /// let mut cdll = CircularDoublyLinkedList::from([1, 2, 3, 4]);
+    /// cdll.rotate_forward(1);
+    /// assert_eq!(format!("{cdll:?}"), "[2, 3, 4, 1]");
+    /// cdll.rotate_forward(6);
+    /// assert_eq!(format!("{cdll:?}"), "[4, 1, 2, 3]");
+    /// ```
+    pub fn rotate_forward(&mut self, n: usize) {
+        if self.len == 0 {
+            return;
+        }
+
+        let n = n % self.len;
+        if n == 0 {
+            return;
+        }
+
+        if n <= self.len / 2 {
+            for _ in 0..n {
+                let next = self.head.as_ref().unwrap().borrow().next.clone();
+                self.head = next;
+            }
+        } else {
+            for _ in n..self.len {
+                self.head = self.tail();
+            }
+        }
+        self.reshaped();
+    }
+
+    /// Moves the head `n` nodes backward along the circle,
+    /// so the current tail becomes the head when `n` is `1`.
+    ///
+    /// Walks whichever way around the circle is shorter,
+    /// so rotating back by one is O(1).
+    ///
+    /// ```
+    /// # use garlic::circular_doubly_linked_list::*;
+    /// let mut cdll = CircularDoublyLinkedList::from([1, 2, 3, 4]);
+    /// cdll.rotate_backward(1);
+    /// assert_eq!(format!("{cdll:?}"), "[4, 1, 2, 3]");
+    /// ```
+    pub fn rotate_backward(&mut self, n: usize) {
+        if self.len == 0 {
+            return;
+        }
+
+        self.rotate_forward(self.len - n % self.len);
+    }
+
+    /// Rotates the list so that the first element matching `predicate`
+    /// becomes the head.
+    ///
+    /// Returns `false` and leaves the list untouched if no element matches.
+    pub fn rotate_to<F>(&mut self, mut predicate: F) -> bool
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let Some(n) = self.iter_once().position(|x| predicate(&x.borrow().value)) else {
+            return false;
+        };
+        self.rotate_forward(n);
+        true
+    }
+
+    /// Whether `other` holds the same elements in the same cyclic order,
+    /// possibly starting from a different head.
+    ///
+    /// ```
+    /// # use garlic::circular_doubly_linked_list::*;
+    /// let cdll = CircularDoublyLinkedList::from([1, 2, 3]);
+    /// assert!(cdll.eq_up_to_rotation(&CircularDoublyLinkedList::from([3, 1, 2])));
+    /// assert!(!cdll.eq_up_to_rotation(&CircularDoublyLinkedList::from([3, 2, 1])));
+    /// ```
+    pub fn eq_up_to_rotation(&self, other: &Self) -> bool
+    where
+        T: PartialEq,
+    {
+        let a: Vec<_> = self.iter_values_once().collect();
+        let b: Vec<_> = other.iter_values_once().collect();
+        is_rotation(&a, b.iter())
+    }
+
+    /// Like [`eq_up_to_rotation`](Self::eq_up_to_rotation),
+    /// but also accepts `other` running around the circle in the opposite direction.
+    ///
+    /// ```
+    /// # use garlic::circular_doubly_linked_list::*;
+    /// let cdll = CircularDoublyLinkedList::from([1, 2, 3, 4]);
+    /// let reflected = CircularDoublyLinkedList::from([2, 1, 4, 3]);
+    /// assert!(cdll.eq_up_to_rotation_and_reflection(&reflected));
+    /// let shuffled = CircularDoublyLinkedList::from([1, 3, 2, 4]);
+    /// assert!(!cdll.eq_up_to_rotation_and_reflection(&shuffled));
+    /// ```
+    pub fn eq_up_to_rotation_and_reflection(&self, other: &Self) -> bool
+    where
+        T: PartialEq,
+    {
+        let a: Vec<_> = self.iter_values_once().collect();
+        let b: Vec<_> = other.iter_values_once().collect();
+        is_rotation(&a, b.iter()) || is_rotation(&a, b.iter().rev())
+    }
+
+    /// Rotates the list so that it starts at its lexicographically smallest rotation,
+    /// see [`CircularLinkedList::canonicalize`].
+    ///
+    /// [`CircularLinkedList::canonicalize`]: crate::circular_linked_list::CircularLinkedList::canonicalize
+    ///
+    /// ```
+    /// # use garlic::circular_doubly_linked_list::*;
+    /// let mut cdll = CircularDoublyLinkedList::from([3, 1, 2, 1, 1]);
+    /// cdll.canonicalize();
+    /// assert_eq!(cdll, CircularDoublyLinkedList::from([1, 1, 3, 1, 2]));
+    /// ```
+    pub fn canonicalize(&mut self)
+    where
+        T: Ord,
+    {
+        let values: Vec<_> = self.iter_values_once().collect();
+        let n = least_rotation(&values);
+        drop(values);
+        self.rotate_forward(n);
+    }
+
+    /// Forgets the collected nodes, after the circle or its head changed.
+    fn reshaped(&mut self) {
+        self.nodes.take();
+    }
+
+    fn tail(&self) -> Pointer<T> {
+        self.head.as_ref()?.borrow().prev.upgrade()
+    }
+
+    /// Returns the node at `index`, walking from whichever end is closer.
+    fn node_at(&self, index: usize) -> Pointer<T> {
+        if index >= self.len {
+            return None;
+        }
+
+        if index <= self.len / 2 {
+            self.iter_once().nth(index)
+        } else {
+            self.iter_once().nth_back(self.len - 1 - index)
+        }
+    }
+
+    /// Pushes into an empty list, creating the self-looping node.
+    fn push_first(&mut self, value: T) {
+        let head = Rc::new_cyclic(|weak| {
+            RefCell::new(Node {
+                value,
+                next: Link(None),
+                prev: weak.clone(),
+            })
+        });
+        *head.borrow_mut().next = Some(head.clone());
+
+        self.head = Some(head);
+        self.len = 1;
+        self.reshaped();
+    }
+
+    /// Links a new node right after `prev` and returns it.
+    ///
+    /// Leaves `head` untouched.
+    fn link_after(prev: &Rcrfn<T>, value: T) -> Rcrfn<T> {
+        let next = prev.borrow_mut().next.take().unwrap();
+        let node = Rc::new(RefCell::new(Node {
+            value,
+            next: Link(Some(next.clone())),
+            prev: Rc::downgrade(prev),
+        }));
+        next.borrow_mut().prev = Rc::downgrade(&node);
+        *prev.borrow_mut().next = Some(node.clone());
+        node
+    }
+
+    /// Unlinks `node` from the circle and returns it,
+    /// keeping `head` pointing into the circle.
+    fn unlink(&mut self, node: Rcrfn<T>) -> Rcrfn<T> {
+        self.reshaped();
+        let next = node.borrow_mut().next.take().unwrap();
+        self.len -= 1;
+
+        if Rc::ptr_eq(&node, &next) {
+            self.head = None;
+            return node;
+        }
+
+        let prev = node.borrow().prev.upgrade().unwrap();
+        next.borrow_mut().prev = Rc::downgrade(&prev);
+        if Rc::ptr_eq(&node, self.head.as_ref().unwrap()) {
+            self.head = Some(next.clone());
+        }
+        *prev.borrow_mut().next = Some(next);
+        node
+    }
+
+    fn into_value(rcrfn: Rcrfn<T>) -> T {
+        match Rc::try_unwrap(rcrfn) {
+            Ok(cell) => cell.into_inner().value,
+            Err(_) => panic!("node is still referenced outside of the list"),
+        }
+    }
+
+    /// Creates an iterator that, by default,
+    /// will never end, unless the list is empty.
+    pub fn iter(&self) -> CdllIter<T> {
+        CdllIter {
+            front: self.head.clone(),
+            back: self.tail(),
+            remaining: None,
+        }
+    }
+
+    /// Creates an iterator that goes through the list once,
+    /// from the head to the tail, or from the tail to the head when reversed.
+    ///
+    /// ```
+    /// # use garlic::circular_doubly_linked_list::*;
+    /// let cdll = CircularDoublyLinkedList::from([1, 2, 3, 4]);
+    /// let mut iter = cdll.iter_once().map(|x| x.borrow().value);
+    /// assert_eq!(iter.next(), Some(1));
+    /// assert_eq!(iter.next_back(), Some(4));
+    /// assert_eq!(iter.collect::<Vec<_>>(), [2, 3]);
+    /// ```
+    pub fn iter_once(&self) -> CdllIter<T> {
+        CdllIter {
+            front: self.head.clone(),
+            back: self.tail(),
+            remaining: Some(self.len),
+        }
+    }
+
+    /// Creates an iterator that starts at the tail and walks backward forever,
+    /// unless the list is empty.
+    ///
+    /// Use `iter_once().rev()` to walk backward only once.
+    pub fn iter_rev(&self) -> std::iter::Rev<CdllIter<T>> {
+        self.iter().rev()
+    }
+
+    /// Creates an iterator over borrowed values that,
+    /// like [`iter`](Self::iter), will never end unless the list is empty.
+    pub fn iter_values(&self) -> Values<'_, T> {
+        Values::new(self.nodes(), None)
+    }
+
+    /// Creates an iterator over borrowed values that goes through the list once.
+    pub fn iter_values_once(&self) -> Values<'_, T> {
+        Values::new(self.nodes(), Some(self.len))
+    }
+
+    /// Creates an iterator over mutably borrowed values that goes through the list once.
+    ///
+    /// There is no never ending version, since a value can't be mutably borrowed twice.
+    ///
+    /// ```
+    /// # use garlic::circular_doubly_linked_list::*;
+    /// let mut cdll = CircularDoublyLinkedList::from([1, 2, 3]);
+    /// for (i, mut x) in cdll.iter_values_mut().rev().enumerate() {
+    ///     *x += i * 10;
+    /// }
+    /// assert_eq!(format!("{cdll:?}"), "[21, 12, 3]");
+    /// ```
+    pub fn iter_values_mut(&mut self) -> ValuesMut<'_, T> {
+        ValuesMut {
+            nodes: self.nodes().iter(),
+        }
+    }
+
+    /// Every node from the head to the tail, see the `nodes` field.
+    fn nodes(&self) -> &[Rcrfn<T>] {
+        self.nodes.get_or_init(|| self.iter_once().collect())
+    }
+
+    /// Creates a cursor starting at the head, for editing the list in place.
+    ///
+    /// ```
+    /// # use garlic::circular_doubly_linked_list::*;
+    /// let mut cdll = CircularDoublyLinkedList::from([1, 2, 3]);
+    /// let mut cursor = cdll.cursor_mut();
+    /// cursor.move_prev();
+    /// *cursor.current().unwrap() *= 10;
+    /// cursor.move_next();
+    /// assert_eq!(cursor.remove_current(), Some(1));
+    /// assert_eq!(cdll, CircularDoublyLinkedList::from([2, 30]));
+    /// ```
+    pub fn cursor_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            current: self.head.clone(),
+            peeked: None,
+            index: 0,
+            list: self,
+        }
+    }
+}
+
+/// Unlinks the nodes one at a time instead of letting the `Rc` chain drop recursively,
+/// so even very long lists can't overflow the stack.
+///
+/// Every node is unlinked, even those still referenced elsewhere,
+/// so an iterator outliving the list yields the node it holds and then stops.
+/// A node that is borrowed while the list drops keeps its link to the next one,
+/// which is released along with it.
+/// Only a list of a single borrowed node, or whose head is mutably borrowed, is leaked,
+/// since the tail is found through the head.
+///
+/// ```
+/// # use garlic::circular_doubly_linked_list::*;
+/// let cdll: CircularDoublyLinkedList<_> = (0..3_000_000).collect();
+/// drop(cdll);
+///
+/// let cdll: CircularDoublyLinkedList<_> = (0..3_000_000).collect();
+/// let mut iter = cdll.iter();
+/// iter.next();
+/// drop(cdll);
+/// assert_eq!(iter.map(|x| x.borrow().value).collect::<Vec<_>>(), [1]);
+///
+/// // Borrowed nodes don't stop the others from being unlinked.
+/// let cdll: CircularDoublyLinkedList<_> = (0..3_000_000).collect();
+/// let second = cdll.iter().nth(1).unwrap();
+/// let borrowed = second.borrow_mut();
+/// drop(cdll);
+/// drop(borrowed);
+/// drop(second);
+/// ```
+///
+/// ```
+/// # use garlic::circular_doubly_linked_list::*;
+/// use std::rc::Rc;
+///
+/// let dropped = Rc::new(());
+/// let cdll: CircularDoublyLinkedList<_> = (0..5).map(|_| dropped.clone()).collect();
+/// let head = cdll.iter().next().unwrap();
+/// let borrowed = head.borrow();
+/// drop(cdll);
+/// // The borrowed head still links to the second node, the rest are gone.
+/// assert_eq!(Rc::strong_count(&dropped), 3);
+/// drop(borrowed);
+/// drop(head);
+/// assert_eq!(Rc::strong_count(&dropped), 1);
+/// ```
+impl<T> Drop for CircularDoublyLinkedList<T> {
+    fn drop(&mut self) {
+        let mut cursor = self.head.take();
+        let tail = cursor
+            .as_ref()
+            .and_then(|head| head.try_borrow().ok()?.prev.upgrade());
+        for _ in 0..self.len {
+            let Some(rcrfn) = cursor else {
+                break;
+            };
+            cursor = match rcrfn.try_borrow_mut() {
+                Ok(mut node) => node.next.take(),
+                // A node borrowed elsewhere keeps its link, but the ones after it can still be unlinked.
+                Err(_) => match rcrfn.try_borrow() {
+                    Ok(node) => node.next.clone(),
+                    Err(_) => {
+                        Self::unlink_back(tail, &rcrfn);
+                        break;
+                    }
+                },
+            };
+        }
+    }
+}
+
+impl<T> CircularDoublyLinkedList<T> {
+    /// Unlinks the nodes from `tail` backward until `stop`, for when `stop` is mutably borrowed
+    /// and the ones after it can't be reached going forward.
+    fn unlink_back(tail: Pointer<T>, stop: &Rcrfn<T>) {
+        let mut cursor = tail;
+        while let Some(rcrfn) = cursor {
+            if Rc::ptr_eq(&rcrfn, stop) {
+                break;
+            }
+            cursor = match rcrfn.try_borrow_mut() {
+                Ok(mut node) => {
+                    node.next.take();
+                    node.prev.upgrade()
+                }
+                Err(_) => None,
+            };
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct CdllIter<T> {
+    front: Pointer<T>,
+    back: Pointer<T>,
+    remaining: Option<usize>,
+}
+
+impl<T> CdllIter<T> {
+    /// Copies the inner value of each `Rc<RefCell<Node<T>>>`
+    ///
+    /// Equivelent to `cdll.map(|x| x.borrow().value)`
+    pub fn map_copied(self) -> impl DoubleEndedIterator<Item = T>
+    where
+        T: Copy,
+    {
+        self.map(|x| x.borrow().value)
+    }
+
+    /// Clones the inner value of each `Rc<RefCell<Node<T>>>`
+    ///
+    /// Equivelent to `cdll.map(|x| x.borrow().value.clone())`
+    pub fn map_cloned(self) -> impl DoubleEndedIterator<Item = T>
+    where
+        T: Clone,
+    {
+        self.map(|x| x.borrow().value.clone())
+    }
+
+    /// Counts down a once around iterator,
+    /// returning `false` once it has gone all the way around.
+    fn count_down(&mut self) -> bool {
+        match &mut self.remaining {
+            None => true,
+            Some(0) => false,
+            Some(remaining) => {
+                *remaining -= 1;
+                true
+            }
+        }
+    }
+}
+
+impl<T> Iterator for CdllIter<T> {
+    type Item = Rcrfn<T>;
+
+    fn next(&mut self) -> Pointer<T> {
+        if !self.count_down() {
+            return None;
+        }
+
+        let r = self.front.take()?;
+        self.front = r.borrow().next.clone();
+        Some(r)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match (&self.front, self.remaining) {
+            (None, _) => (0, Some(0)),
+            (Some(_), None) => (usize::MAX, None),
+            (Some(_), Some(remaining)) => (remaining, Some(remaining)),
+        }
+    }
+}
+
+impl<T> DoubleEndedIterator for CdllIter<T> {
+    fn next_back(&mut self) -> Pointer<T> {
+        if !self.count_down() {
+            return None;
+        }
+
+        let r = self.back.take()?;
+        self.back = r.borrow().prev.upgrade();
+        Some(r)
+    }
+}
+
+/// Only meaningful for iterators created with [`CircularDoublyLinkedList::iter_once`].
+/// Calling [`len`](ExactSizeIterator::len) on a never ending iterator panics.
+impl<T> ExactSizeIterator for CdllIter<T> {}
+
+/// A cursor over a [`CircularDoublyLinkedList`] that can insert, remove and replace elements.
+///
+/// Since the list is circular, the cursor never runs off either end:
+/// moving past the tail brings it back to the head, and the other way around.
+///
+/// Created by [`CircularDoublyLinkedList::cursor_mut`].
+pub struct CursorMut<'a, T> {
+    list: &'a mut CircularDoublyLinkedList<T>,
+    current: Pointer<T>,
+    /// Keeps the node [`peek_next`](Self::peek_next) or [`peek_prev`](Self::peek_prev)
+    /// borrows alive.
+    peeked: Pointer<T>,
+    index: usize,
+}
+
+impl<T> CursorMut<'_, T> {
+    /// Index of the current element, counting from the head,
+    /// or `None` if the list is empty.
+    pub fn index(&self) -> Option<usize> {
+        self.current.as_ref().map(|_| self.index)
+    }
+
+    /// Moves to the next element, wrapping from the tail back to the head.
+    pub fn move_next(&mut self) {
+        let Some(current) = self.current.take() else {
+            return;
+        };
+        self.current = current.borrow().next.clone();
+        self.index = (self.index + 1) % self.list.len;
+    }
+
+    /// Moves to the previous element, wrapping from the head back to the tail.
+    pub fn move_prev(&mut self) {
+        let Some(current) = self.current.take() else {
+            return;
+        };
+        self.current = current.borrow().prev.upgrade();
+        self.index = (self.index + self.list.len - 1) % self.list.len;
+    }
+
+    /// Borrows the current element, or returns `None` if the list is empty.
+    pub fn current(&mut self) -> Option<RefMut<'_, T>> {
+        let node = self.current.as_deref()?;
+        Some(RefMut::map(node.borrow_mut(), |n| &mut n.value))
+    }
+
+    /// Borrows the element after the current one, or returns `None` if the list is empty.
+    ///
+    /// In a single element list, this is the current element itself.
+    pub fn peek_next(&mut self) -> Option<RefMut<'_, T>> {
+        self.peeked = self.current.as_ref()?.borrow().next.clone();
+        let node = self.peeked.as_ref().unwrap();
+        Some(RefMut::map(node.borrow_mut(), |n| &mut n.value))
+    }
+
+    /// Borrows the element before the current one, or returns `None` if the list is empty.
+    ///
+    /// In a single element list, this is the current element itself.
+    pub fn peek_prev(&mut self) -> Option<RefMut<'_, T>> {
+        self.peeked = self.current.as_ref()?.borrow().prev.upgrade();
+        let node = self.peeked.as_ref().unwrap();
+        Some(RefMut::map(node.borrow_mut(), |n| &mut n.value))
+    }
+
+    /// Inserts an element after the current one.
+    ///
+    /// If the list is empty, the new element becomes the current one.
+    pub fn insert_after(&mut self, value: T) {
+        let Some(current) = &self.current else {
+            return self.insert_into_empty(value);
+        };
+
+        CircularDoublyLinkedList::link_after(current, value);
+        self.list.len += 1;
+        self.list.reshaped();
+    }
+
+    /// Inserts an element before the current one.
+    ///
+    /// If the current element is the head, the new element becomes the head.
+    /// If the list is empty, the new element becomes the current one.
+    ///
+    /// ```
+    /// # use garlic::circular_doubly_linked_list::*;
+    /// let mut cdll = CircularDoublyLinkedList::from([2, 4]);
+    /// let mut cursor = cdll.cursor_mut();
+    /// cursor.insert_before(1);
+    /// cursor.move_next();
+    /// cursor.insert_before(3);
+    /// cursor.insert_after(5);
+    /// assert_eq!(cursor.index(), Some(3));
+    /// assert_eq!(cdll, CircularDoublyLinkedList::from([1, 2, 3, 4, 5]));
+    /// ```
+    pub fn insert_before(&mut self, value: T) {
+        let Some(current) = &self.current else {
+            return self.insert_into_empty(value);
+        };
+
+        let prev = current.borrow().prev.upgrade().unwrap();
+        let node = CircularDoublyLinkedList::link_after(&prev, value);
+        self.list.len += 1;
+        if Rc::ptr_eq(current, self.list.head.as_ref().unwrap()) {
+            self.list.head = Some(node);
+        }
+        self.list.reshaped();
+        self.index += 1;
+    }
+
+    fn insert_into_empty(&mut self, value: T) {
+        self.list.push_first(value);
+        self.current = self.list.head.clone();
+        self.index = 0;
+    }
+
+    /// Removes the current element and returns it, moving the cursor to the next element.
+    ///
+    /// Returns `None` if the list is empty.
+    ///
+    /// # Panics
+    /// Panics if the node is still referenced elsewhere, e.g. by a live [`CdllIter`].
+    pub fn remove_current(&mut self) -> Option<T> {
+        let current = self.current.take()?;
+        self.peeked = None;
+        let next = current.borrow().next.clone();
+        let node = self.list.unlink(current);
+
+        if self.list.is_empty() {
+            drop(next);
+            self.index = 0;
+        } else {
+            self.current = next;
+            self.index %= self.list.len;
+        }
+        Some(CircularDoublyLinkedList::into_value(node))
+    }
+
+    /// Replaces the current element, returning the old one.
+    ///
+    /// If the list is empty, `value` is inserted as the only element and `None` is returned.
+    pub fn replace_current(&mut self, value: T) -> Option<T> {
+        let Some(current) = &self.current else {
+            self.insert_into_empty(value);
+            return None;
+        };
+        Some(std::mem::replace(&mut current.borrow_mut().value, value))
+    }
+
+    /// Splits the list after the current element.
+    ///
+    /// The cursor's list keeps the elements from the head up to and including the current one,
+    /// everything after it, up to the tail, is returned as a new list.
+    ///
+    /// ```
+    /// # use garlic::circular_doubly_linked_list::*;
+    /// let mut cdll = CircularDoublyLinkedList::from([1, 2, 3, 4, 5]);
+    /// let mut cursor = cdll.cursor_mut();
+    /// cursor.move_next();
+    /// let rest = cursor.split_after();
+    /// assert_eq!(cdll, CircularDoublyLinkedList::from([1, 2]));
+    /// assert_eq!(rest.iter_once().map_copied().rev().collect::<Vec<_>>(), [5, 4, 3]);
+    /// ```
+    pub fn split_after(&mut self) -> CircularDoublyLinkedList<T> {
+        let mut rest = CircularDoublyLinkedList::new();
+        let Some(current) = &self.current else {
+            return rest;
+        };
+        let tail = self.list.tail().unwrap();
+        if Rc::ptr_eq(current, &tail) {
+            return rest;
+        }
+
+        let head = self.list.head.clone().unwrap();
+        head.borrow_mut().prev = Rc::downgrade(current);
+        let first = current.borrow_mut().next.replace(head).unwrap();
+        first.borrow_mut().prev = Rc::downgrade(&tail);
+        *tail.borrow_mut().next = Some(first.clone());
+
+        rest.head = Some(first);
+        rest.len = self.list.len - self.index - 1;
+        self.list.len = self.index + 1;
+        self.list.reshaped();
+        rest
+    }
+}
+
+/// Iterator over borrowed values of a [`CircularDoublyLinkedList`].
+///
+/// Created by [`CircularDoublyLinkedList::iter_values`]
+/// and [`CircularDoublyLinkedList::iter_values_once`].
+///
+/// The values are borrowed from nodes the list has collected up front,
+/// so they stay valid even if the nodes are relinked through a [`CdllIter`] meanwhile.
+///
+/// ```
+/// # use garlic::circular_doubly_linked_list::*;
+/// let a = CircularDoublyLinkedList::from(['a', 'b', 'c']);
+/// let b = CircularDoublyLinkedList::from(['e', 'd']);
+/// let mut values = a.iter_values_once();
+/// assert_eq!(values.next().as_deref(), Some(&'a'));
+///
+/// // Swapping whole nodes swaps their links too.
+/// let (node_a, node_d) = (a.iter().next().unwrap(), b.iter().nth(1).unwrap());
+/// std::mem::swap(&mut *node_a.borrow_mut(), &mut *node_d.borrow_mut());
+/// drop((node_a, node_d, b));
+/// assert_eq!(values.next_back().as_deref(), Some(&'c'));
+/// assert_eq!(values.next().as_deref(), Some(&'b'));
+/// assert!(values.next().is_none());
+/// ```
+#[derive(Clone)]
+pub struct Values<'a, T> {
+    nodes: &'a [Rcrfn<T>],
+    front: usize,
+    back: usize,
+    remaining: Option<usize>,
+}
+
+impl<'a, T> Values<'a, T> {
+    fn new(nodes: &'a [Rcrfn<T>], remaining: Option<usize>) -> Self {
+        Self {
+            nodes,
+            front: 0,
+            back: nodes.len().saturating_sub(1),
+            remaining,
+        }
+    }
+
+    fn count_down(&mut self) -> bool {
+        match &mut self.remaining {
+            None => !self.nodes.is_empty(),
+            Some(0) => false,
+            Some(remaining) => {
+                *remaining -= 1;
+                true
+            }
+        }
+    }
+}
+
+impl<'a, T> Iterator for Values<'a, T> {
+    type Item = Ref<'a, T>;
+
+    fn next(&mut self) -> Option<Ref<'a, T>> {
+        if !self.count_down() {
+            return None;
+        }
+
+        let nodes = self.nodes;
+        let node = &nodes[self.front];
+        self.front = (self.front + 1) % nodes.len();
+        Some(Ref::map(node.borrow(), |n| &n.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match (self.nodes.is_empty(), self.remaining) {
+            (true, _) => (0, Some(0)),
+            (false, None) => (usize::MAX, None),
+            (false, Some(remaining)) => (remaining, Some(remaining)),
+        }
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Values<'a, T> {
+    fn next_back(&mut self) -> Option<Ref<'a, T>> {
+        if !self.count_down() {
+            return None;
+        }
+
+        let nodes = self.nodes;
+        let node = &nodes[self.back];
+        self.back = (self.back + nodes.len() - 1) % nodes.len();
+        Some(Ref::map(node.borrow(), |n| &n.value))
+    }
+}
+
+/// Iterator over mutably borrowed values of a [`CircularDoublyLinkedList`].
+///
+/// Created by [`CircularDoublyLinkedList::iter_values_mut`].
+pub struct ValuesMut<'a, T> {
+    nodes: std::slice::Iter<'a, Rcrfn<T>>,
+}
+
+impl<'a, T> Iterator for ValuesMut<'a, T> {
+    type Item = RefMut<'a, T>;
+
+    fn next(&mut self) -> Option<RefMut<'a, T>> {
+        let node = self.nodes.next()?;
+        Some(RefMut::map(node.borrow_mut(), |n| &mut n.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.nodes.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for ValuesMut<'a, T> {
+    fn next_back(&mut self) -> Option<RefMut<'a, T>> {
+        let node = self.nodes.next_back()?;
+        Some(RefMut::map(node.borrow_mut(), |n| &mut n.value))
+    }
+}
+
+impl<T> ExactSizeIterator for ValuesMut<'_, T> {}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for CircularDoublyLinkedList<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        let mut l = f.debug_list();
+        for rcrfn in self.iter_once() {
+            l.entry(&rcrfn.borrow().value);
+        }
+        l.finish()
+    }
+}
+
+/// Deep clone: every node is reallocated, no `Rc` is shared with the original.
+impl<T: Clone> Clone for CircularDoublyLinkedList<T> {
+    fn clone(&self) -> Self {
+        self.iter_values_once().map(|x| T::clone(&x)).collect()
+    }
+}
+
+/// Lists are equal if they have the same elements in the same order, starting from the head.
+impl<T: PartialEq> PartialEq for CircularDoublyLinkedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len
+            && self
+                .iter_values_once()
+                .zip(other.iter_values_once())
+                .all(|(a, b)| *a == *b)
+    }
+}
+
+impl<T: Eq> Eq for CircularDoublyLinkedList<T> {}
+
+impl<T: Hash> Hash for CircularDoublyLinkedList<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len.hash(state);
+        for x in self.iter_values_once() {
+            x.hash(state);
+        }
+    }
+}
+
+/// Lexicographic comparison, starting from the head.
+impl<T: PartialOrd> PartialOrd for CircularDoublyLinkedList<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        for (a, b) in self.iter_values_once().zip(other.iter_values_once()) {
+            match (*a).partial_cmp(&*b) {
+                Some(Ordering::Equal) => {}
+                non_eq => return non_eq,
+            }
+        }
+        self.len.partial_cmp(&other.len)
+    }
+}
+
+impl<T: Ord> Ord for CircularDoublyLinkedList<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        for (a, b) in self.iter_values_once().zip(other.iter_values_once()) {
+            match (*a).cmp(&*b) {
+                Ordering::Equal => {}
+                non_eq => return non_eq,
+            }
+        }
+        self.len.cmp(&other.len)
+    }
+}
+
+impl<T> std::iter::FromIterator<T> for CircularDoublyLinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut cdll = Self::new();
+        cdll.extend(iter);
+        cdll
+    }
+}
+
+impl<T> Extend<T> for CircularDoublyLinkedList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.push(x);
+        }
+    }
+}
+
+impl<'a, T: Copy + 'a> Extend<&'a T> for CircularDoublyLinkedList<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<T> From<Vec<T>> for CircularDoublyLinkedList<T> {
+    fn from(vec: Vec<T>) -> Self {
+        vec.into_iter().collect()
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for CircularDoublyLinkedList<T> {
+    fn from(array: [T; N]) -> Self {
+        array.into_iter().collect()
+    }
+}
+
+impl<T> From<VecDeque<T>> for CircularDoublyLinkedList<T> {
+    fn from(deque: VecDeque<T>) -> Self {
+        deque.into_iter().collect()
+    }
+}
+
+impl<T> From<CircularDoublyLinkedList<T>> for Vec<T> {
+    fn from(cdll: CircularDoublyLinkedList<T>) -> Self {
+        cdll.into_iter().collect()
+    }
+}
+
+/// Collects the elements starting at the head.
+///
+/// ```
+/// # use garlic::circular_doubly_linked_list::*;
+/// # use std::collections::{LinkedList, VecDeque};
+/// let mut cdll = CircularDoublyLinkedList::from(VecDeque::from([1, 2, 3]));
+/// cdll.rotate_backward(1);
+/// assert_eq!(VecDeque::from(cdll.clone()), [3, 1, 2]);
+/// assert_eq!(LinkedList::from(cdll), LinkedList::from([3, 1, 2]));
+/// ```
+impl<T> From<CircularDoublyLinkedList<T>> for VecDeque<T> {
+    fn from(cdll: CircularDoublyLinkedList<T>) -> Self {
+        cdll.into_iter().collect()
+    }
+}
+
+impl<T> From<CircularDoublyLinkedList<T>> for LinkedList<T> {
+    fn from(cdll: CircularDoublyLinkedList<T>) -> Self {
+        cdll.into_iter().collect()
+    }
+}
+
+/// Consumes the list, yielding each element once, from the head or from the tail.
+///
+/// ```
+/// # use garlic::circular_doubly_linked_list::*;
+/// let cdll = CircularDoublyLinkedList::from([1, 2, 3]);
+/// assert_eq!(cdll.into_iter().rev().collect::<Vec<_>>(), [3, 2, 1]);
+/// ```
+impl<T> IntoIterator for CircularDoublyLinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { list: self }
+    }
+}
+
+/// Iterates through the list once, like [`CircularDoublyLinkedList::iter_once`].
+impl<T> IntoIterator for &CircularDoublyLinkedList<T> {
+    type Item = Rcrfn<T>;
+    type IntoIter = CdllIter<T>;
+
+    fn into_iter(self) -> CdllIter<T> {
+        self.iter_once()
+    }
+}
+
+/// Owning iterator over the elements of a [`CircularDoublyLinkedList`].
+///
+/// Created by [`CircularDoublyLinkedList::into_iter`].
+pub struct IntoIter<T> {
+    list: CircularDoublyLinkedList<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len(), Some(self.list.len()))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.list.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
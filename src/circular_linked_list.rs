@@ -1,6 +1,16 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    cell::{BorrowError, BorrowMutError, Cell, OnceCell, Ref, RefCell, RefMut},
+    cmp::Ordering,
+    collections::{LinkedList, VecDeque},
+    hash::{Hash, Hasher},
+    ops::{Bound, Deref, DerefMut, Index, RangeBounds},
+    rc::{Rc, Weak},
+};
 
-/// A circular singly linked list.
+/// A circular linked list.
+///
+/// Every node owns the link to the next one,
+/// and keeps a [`Weak`] link back to the one before it.
 ///
 ///
 /// [`Cycle`]: std::iter::Cycle
@@ -32,28 +42,186 @@ use std::{cell::RefCell, rc::Rc};
 pub struct CircularLinkedList<T> {
     head: Pointer<T>,
     tail: Pointer<T>,
+    len: usize,
+    owner: Rc<Owner>,
+    shape: Rc<Shape<T>>,
+    /// Every node from the head to the tail, collected when values are borrowed,
+    /// so the borrows can't outlive the nodes whatever happens to their links.
+    /// Cleared by every structural change.
+    nodes: OnceCell<Vec<Element<T>>>,
+}
+
+/// The list's shape as last recorded, shared with its [`CllIter`]s
+/// so they can tell when it has been modified under them.
+struct Shape<T> {
+    /// Bumped by every structural change.
+    generation: Cell<u64>,
+    tail: RefCell<Weak<RefCell<Node<T>>>>,
+    len: Cell<usize>,
+}
+
+impl<T> Default for Shape<T> {
+    fn default() -> Self {
+        Self {
+            generation: Cell::new(0),
+            tail: RefCell::new(Weak::new()),
+            len: Cell::new(0),
+        }
+    }
 }
 
 pub struct Node<T> {
     pub value: T,
-    next: Pointer<T>,
+    next: Link<T>,
+    /// The node linking to this one, so it can be unlinked without walking the circle.
+    prev: Weak<RefCell<Node<T>>>,
+    /// The list this node belongs to, `None` once it has been unlinked.
+    owner: Option<Rc<Owner>>,
+}
+
+/// Identifies which list a node belongs to, so [`NodeHandle`]s can be validated.
+#[derive(Default)]
+struct Owner {
+    /// Set when every node of this owner's list moves into another list at once,
+    /// so they don't all have to be retagged.
+    forward: RefCell<Option<Rc<Owner>>>,
+}
+
+impl Owner {
+    /// Follows forwarded owners to the one of the list the node currently belongs to.
+    ///
+    /// Every owner on the way is then forwarded straight to it,
+    /// so lists moved as a whole over and over don't make later lookups walk a long chain.
+    fn root(self: &Rc<Self>) -> Rc<Self> {
+        let mut root = self.clone();
+        loop {
+            let forward = root.forward.borrow().clone();
+            match forward {
+                Some(next) => root = next,
+                None => break,
+            }
+        }
+
+        let mut owner = self.clone();
+        while !Rc::ptr_eq(&owner, &root) {
+            let next = owner.forward.replace(Some(root.clone())).unwrap();
+            owner = next;
+        }
+        root
+    }
+}
+
+/// Drops a chain of forwarded owners one at a time, so a long one can't overflow the stack.
+impl Drop for Owner {
+    fn drop(&mut self) {
+        let mut forward = self.forward.get_mut().take();
+        while let Some(owner) = forward {
+            forward = Rc::try_unwrap(owner)
+                .ok()
+                .and_then(|owner| owner.forward.take());
+        }
+    }
 }
 
 type Rcrfn<T> = Rc<RefCell<Node<T>>>;
 type Pointer<T> = Option<Rcrfn<T>>;
 
+/// The owning link from a node to the next one.
+///
+/// Drops the chain behind it one node at a time, instead of letting it drop recursively,
+/// so releasing the last reference to a long detached chain can't overflow the stack.
+struct Link<T>(Pointer<T>);
+
+impl<T> Deref for Link<T> {
+    type Target = Pointer<T>;
+
+    fn deref(&self) -> &Pointer<T> {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Link<T> {
+    fn deref_mut(&mut self) -> &mut Pointer<T> {
+        &mut self.0
+    }
+}
+
+impl<T> Drop for Link<T> {
+    fn drop(&mut self) {
+        let mut next = self.0.take();
+        while let Some(node) = next {
+            next = Rc::try_unwrap(node)
+                .ok()
+                .and_then(|cell| cell.into_inner().next.take());
+        }
+    }
+}
+
+/// Why a `try_` method of [`CircularLinkedList`] couldn't do its job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CllError {
+    /// The list has no elements.
+    Empty,
+    /// The index is past the end of the list.
+    OutOfBounds,
+    /// A node is borrowed elsewhere, e.g. through an `Rc` handed out by a [`CllIter`].
+    AlreadyBorrowed,
+    /// A node is still referenced outside of the list, e.g. by a live [`CllIter`],
+    /// so its value can't be moved out.
+    NodeInUse,
+    /// The [`NodeHandle`] doesn't refer to an element of this list.
+    ForeignNode,
+}
+
+impl std::fmt::Display for CllError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Empty => "list is empty",
+            Self::OutOfBounds => "index out of bounds",
+            Self::AlreadyBorrowed => "node is already borrowed",
+            Self::NodeInUse => "node is still referenced outside of the list",
+            Self::ForeignNode => "node doesn't belong to this list",
+        })
+    }
+}
+
+impl std::error::Error for CllError {}
+
+impl From<BorrowError> for CllError {
+    fn from(_: BorrowError) -> Self {
+        Self::AlreadyBorrowed
+    }
+}
+
+impl From<BorrowMutError> for CllError {
+    fn from(_: BorrowMutError) -> Self {
+        Self::AlreadyBorrowed
+    }
+}
+
+/// Turns the errors that mean "nothing there" into `None`, and panics on the rest.
+fn found<V>(result: Result<V, CllError>) -> Option<V> {
+    match result {
+        Ok(v) => Some(v),
+        Err(CllError::Empty | CllError::OutOfBounds | CllError::ForeignNode) => None,
+        Err(e) => panic!("{e}"),
+    }
+}
+
 impl<T> CircularLinkedList<T> {
     pub fn new() -> Self {
         Self {
             head: None,
             tail: None,
+            len: 0,
+            owner: Rc::default(),
+            shape: Rc::default(),
+            nodes: OnceCell::new(),
         }
     }
 
-    /// ## Expensive
-    /// Has to traverse entire list.
     pub fn len(&self) -> usize {
-        self.iter_once().count()
+        self.len
     }
 
     pub fn is_empty(&self) -> bool {
@@ -61,113 +229,3183 @@ impl<T> CircularLinkedList<T> {
     }
 
     pub fn push(&mut self, value: T) {
-        let Some(rcrfn) = &self.tail else {
-            let node = Node { value, next: None };
-            let head = Rc::new(RefCell::new(node));
-            head.borrow_mut().next = Some(head.clone());
+        let Some(tail) = self.tail.clone() else {
+            return self.push_first(value);
+        };
+        self.tail = Some(Self::link_after(&tail, value));
+        self.len += 1;
+        self.reshaped();
+    }
+
+    /// Pushes an element in front of the head, making it the new head.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut cll = CircularLinkedList::new();
+    /// cll.push_front(2);
+    /// cll.push_front(1);
+    /// cll.push(3);
+    /// assert_eq!(format!("{cll:?}"), "[1, 2, 3]");
+    /// ```
+    pub fn push_front(&mut self, value: T) {
+        let Some(tail) = self.tail.clone() else {
+            return self.push_first(value);
+        };
+        self.head = Some(Self::link_after(&tail, value));
+        self.len += 1;
+        self.reshaped();
+    }
+
+    /// Inserts an element so that it ends up at position `index`.
+    ///
+    /// `index` wraps around the circle, so inserting at `len` is the same as
+    /// inserting at `0`: the new element becomes the head.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut cll: CircularLinkedList<_> = "ace".chars().collect();
+    /// cll.insert(1, 'b');
+    /// cll.insert(7, 'd');
+    /// assert_eq!(format!("{cll:?}"), "['a', 'b', 'c', 'd', 'e']");
+    ///
+    /// cll.insert(5, 'z');
+    /// assert_eq!(format!("{cll:?}"), "['z', 'a', 'b', 'c', 'd', 'e']");
+    /// ```
+    pub fn insert(&mut self, index: usize, value: T) {
+        let len = self.len();
+        if len == 0 {
+            return self.push_first(value);
+        }
+
+        match index % len {
+            0 => self.push_front(value),
+            i => {
+                let prev = self.node_at(i - 1).unwrap();
+                Self::link_after(&prev, value);
+                self.len += 1;
+                self.reshaped();
+            }
+        }
+    }
+
+    /// Pushes into an empty list, creating the self-looping node.
+    fn push_first(&mut self, value: T) {
+        let node = Node {
+            value,
+            next: Link(None),
+            prev: Weak::new(),
+            owner: Some(self.owner.clone()),
+        };
+        let head = Rc::new(RefCell::new(node));
+        *head.borrow_mut().next = Some(head.clone());
+        head.borrow_mut().prev = Rc::downgrade(&head);
+
+        self.head = Some(head.clone());
+        self.tail = Some(head);
+        self.len = 1;
+        self.reshaped();
+    }
+
+    /// Records a structural change, so iterators created before it can notice.
+    ///
+    /// Must be called by every method that links, unlinks or rotates nodes,
+    /// once `head`, `tail` and `len` are up to date.
+    fn reshaped(&mut self) {
+        self.nodes.take();
+        let shape = &self.shape;
+        shape.generation.set(shape.generation.get() + 1);
+        *shape.tail.borrow_mut() = self.tail.as_ref().map(Rc::downgrade).unwrap_or_default();
+        shape.len.set(self.len);
+    }
 
-            self.head = Some(head.clone());
-            self.tail = Some(head);
+    /// Points every node back at the one linking to it, after many were relinked at once.
+    fn relink_prevs(&self) {
+        let Some(mut prev) = self.tail.clone() else {
             return;
         };
+        for node in self.iter_once() {
+            node.borrow_mut().prev = Rc::downgrade(&prev);
+            prev = node;
+        }
+    }
 
-        let next = Node {
+    /// Links a new node right after `prev` and returns it.
+    ///
+    /// Leaves `head` and `tail` untouched.
+    fn link_after(prev: &Rcrfn<T>, value: T) -> Rcrfn<T> {
+        let mut prev_node = prev.borrow_mut();
+        let next = prev_node.next.take().unwrap();
+        let node = Node {
             value,
-            next: self.head.clone(),
+            next: Link(Some(next.clone())),
+            prev: Rc::downgrade(prev),
+            owner: prev_node.owner.clone(),
         };
-        let next_ptr = Some(Rc::new(RefCell::new(next)));
-        rcrfn.borrow_mut().next = next_ptr.clone();
-        self.tail = next_ptr.clone();
+        let node_ptr = Rc::new(RefCell::new(node));
+        *prev_node.next = Some(node_ptr.clone());
+        drop(prev_node);
+        next.borrow_mut().prev = Rc::downgrade(&node_ptr);
+        node_ptr
     }
 
-    /// Creates an iterator that, by default,
-    /// will never end, unless the list is empty.
-    pub fn iter(&self) -> CllIter<T> {
-        CllIter {
-            cursor: self.head.clone(),
-            tail: self.tail.clone(),
-            stop: false,
+    /// Removes the head element and returns it, or `None` if the list is empty.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut cll: CircularLinkedList<_> = (1..=3).collect();
+    /// assert_eq!(cll.pop_front(), Some(1));
+    /// assert_eq!(cll.pop_front(), Some(2));
+    /// assert_eq!(cll.pop_front(), Some(3));
+    /// assert_eq!(cll.pop_front(), None);
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if the node is still referenced elsewhere, e.g. by a live [`CllIter`],
+    /// or if it or the tail is borrowed.
+    /// See [`try_pop_front`](Self::try_pop_front) for a non-panicking version.
+    pub fn pop_front(&mut self) -> Option<T> {
+        found(self.try_pop_front())
+    }
+
+    /// Removes the tail element and returns it, or `None` if the list is empty.
+    ///
+    /// Runs in constant time, the tail knows the node before it.
+    ///
+    /// # Panics
+    /// Panics if the node is still referenced elsewhere, e.g. by a live [`CllIter`],
+    /// or if it or the node before it is borrowed.
+    /// See [`try_pop_back`](Self::try_pop_back) for a non-panicking version.
+    pub fn pop_back(&mut self) -> Option<T> {
+        found(self.try_pop_back())
+    }
+
+    /// Removes the element at `index` and returns it,
+    /// or `None` if `index` is out of bounds.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut cll: CircularLinkedList<_> = "abcd".chars().collect();
+    /// assert_eq!(cll.remove(2), Some('c'));
+    /// assert_eq!(cll.remove(3), None);
+    /// assert_eq!(format!("{cll:?}"), "['a', 'b', 'd']");
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if the node is still referenced elsewhere, e.g. by a live [`CllIter`],
+    /// or if any node on the way is borrowed.
+    /// See [`try_remove`](Self::try_remove) for a non-panicking version.
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        found(self.try_remove(index))
+    }
+
+    /// Like [`push`](Self::push),
+    /// but fails instead of panicking if the tail or the head is borrowed.
+    ///
+    /// `value` is dropped on failure.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut cll = CircularLinkedList::from([1, 2]);
+    /// let tail = cll.iter().nth(1).unwrap();
+    /// let guard = tail.borrow_mut();
+    /// assert_eq!(cll.try_push(3), Err(CllError::AlreadyBorrowed));
+    /// drop(guard);
+    ///
+    /// let head = cll.iter().next().unwrap();
+    /// let guard = head.borrow();
+    /// assert_eq!(cll.try_push(3), Err(CllError::AlreadyBorrowed));
+    /// assert_eq!(cll.try_push_front(0), Err(CllError::AlreadyBorrowed));
+    /// drop(guard);
+    /// assert_eq!(cll.try_push(3), Ok(()));
+    /// assert_eq!(cll, CircularLinkedList::from([1, 2, 3]));
+    /// ```
+    pub fn try_push(&mut self, value: T) -> Result<(), CllError> {
+        self.check_push()?;
+        self.push(value);
+        Ok(())
+    }
+
+    /// Like [`push_front`](Self::push_front),
+    /// but fails instead of panicking if the tail or the head is borrowed.
+    ///
+    /// `value` is dropped on failure.
+    pub fn try_push_front(&mut self, value: T) -> Result<(), CllError> {
+        self.check_push()?;
+        self.push_front(value);
+        Ok(())
+    }
+
+    /// Checks that linking a node between the tail and the head won't panic.
+    fn check_push(&self) -> Result<(), CllError> {
+        if let (Some(head), Some(tail)) = (&self.head, &self.tail) {
+            tail.try_borrow_mut()?;
+            head.try_borrow_mut()?;
         }
+        Ok(())
     }
 
-    /// Creates an iterator that, by default,
-    /// will iterate throught the list and stop at the tail element.
-    pub fn iter_once(&self) -> CllIter<T> {
-        self.iter().once()
+    /// Like [`pop_front`](Self::pop_front), but reports every failure as a [`CllError`].
+    ///
+    /// The list is left untouched on failure.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut cll = CircularLinkedList::from([1, 2]);
+    /// let mut iter = cll.iter_once();
+    /// assert_eq!(cll.try_pop_front(), Err(CllError::NodeInUse));
+    /// iter.next();
+    /// assert_eq!(cll.try_pop_front(), Ok(1));
+    /// drop(iter);
+    /// assert_eq!(cll.try_pop_front(), Ok(2));
+    /// assert_eq!(cll.try_pop_front(), Err(CllError::Empty));
+    ///
+    /// // The node after the removed one is relinked, so it can't be borrowed either.
+    /// let mut cll = CircularLinkedList::from([1, 2, 3]);
+    /// let second = cll.iter().nth(1).unwrap();
+    /// let guard = second.borrow();
+    /// assert_eq!(cll.try_pop_front(), Err(CllError::AlreadyBorrowed));
+    /// assert_eq!(cll.len(), 3);
+    /// drop(guard);
+    /// drop(second);
+    /// assert_eq!(cll, CircularLinkedList::from([1, 2, 3]));
+    /// ```
+    pub fn try_pop_front(&mut self) -> Result<T, CllError> {
+        if self.is_empty() {
+            return Err(CllError::Empty);
+        }
+        self.try_remove(0)
     }
-}
 
-impl<T> Drop for CircularLinkedList<T> {
-    fn drop(&mut self) {
-        if let Some(rcrfn) = self.tail.as_mut() {
-            rcrfn.borrow_mut().next = None;
+    /// Like [`pop_back`](Self::pop_back), but reports every failure as a [`CllError`].
+    ///
+    /// The list is left untouched on failure.
+    pub fn try_pop_back(&mut self) -> Result<T, CllError> {
+        let removed = {
+            let tail = self.tail.as_ref().ok_or(CllError::Empty)?;
+            let prev = tail.try_borrow()?.prev.upgrade().unwrap();
+            self.check_unlink_after(&prev)?;
+            self.unlink_after(&prev)
+        };
+        Ok(Self::into_value(removed))
+    }
+
+    /// Like [`remove`](Self::remove), but reports every failure as a [`CllError`].
+    ///
+    /// The list is left untouched on failure.
+    pub fn try_remove(&mut self, index: usize) -> Result<T, CllError> {
+        if index >= self.len {
+            return Err(CllError::OutOfBounds);
         }
+
+        let node = {
+            let prev = match index {
+                0 => self.tail.clone().unwrap(),
+                _ => self.try_node_at(index - 1)?,
+            };
+            self.check_unlink_after(&prev)?;
+            self.unlink_after(&prev)
+        };
+        Ok(Self::into_value(node))
     }
-}
 
-#[derive(Clone)]
-pub struct CllIter<T> {
-    cursor: Pointer<T>,
-    tail: Pointer<T>,
-    stop: bool,
-}
+    /// Keeps the first `len` elements and drops the rest.
+    ///
+    /// Does nothing if `len` is greater than or equal to the list's current length.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut cll: CircularLinkedList<_> = (1..=5).collect();
+    /// cll.truncate(2);
+    /// assert_eq!(format!("{cll:?}"), "[1, 2]");
+    /// assert_eq!(cll.iter().map_copied().take(4).collect::<Vec<_>>(), [1, 2, 1, 2]);
+    /// ```
+    pub fn truncate(&mut self, len: usize) {
+        if len == 0 {
+            return self.clear();
+        }
 
-impl<T> CllIter<T> {
-    pub fn once(mut self) -> Self {
-        self.stop = true;
-        self
+        let Some(new_tail) = self.node_at(len - 1) else {
+            return;
+        };
+        while !Rc::ptr_eq(&new_tail, self.tail.as_ref().unwrap()) {
+            self.unlink_after(&new_tail);
+        }
     }
 
-    /// Copies the inner value of each `Rc<RefCell<Node<T>>>`
+    /// Removes all elements.
+    pub fn clear(&mut self) {
+        // Keep the shape, so iterators over the old elements notice they're gone.
+        let shape = self.shape.clone();
+        *self = Self::new();
+        self.shape = shape;
+        self.reshaped();
+    }
+
+    /// Moves all elements of `other` after the tail, leaving `other` empty.
     ///
-    /// Equivelent to `cll.map(|x| x.borrow().value)`
-    pub fn map_copied(self) -> impl Iterator<Item = T>
+    /// Runs in constant time: the two circles are cut open and joined,
+    /// and `other`'s nodes are retagged all at once, so their [`NodeHandle`]s
+    /// now refer to elements of this list.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut a = CircularLinkedList::from([1, 2]);
+    /// let mut b = CircularLinkedList::from([3, 4]);
+    /// let three = b.handle_at(0).unwrap();
+    ///
+    /// a.append(&mut b);
+    /// assert_eq!(a, CircularLinkedList::from([1, 2, 3, 4]));
+    /// assert!(b.is_empty());
+    /// assert!(a.contains_node(&three));
+    /// assert!(!b.contains_node(&three));
+    /// assert_eq!(a.iter().map_copied().nth(4), Some(1));
+    /// ```
+    pub fn append(&mut self, other: &mut Self) {
+        let (Some(other_head), Some(other_tail)) = (other.head.take(), other.tail.take()) else {
+            return;
+        };
+
+        match &self.tail {
+            Some(tail) => {
+                let head = self.head.as_ref().unwrap();
+                other_head.borrow_mut().prev = Rc::downgrade(tail);
+                head.borrow_mut().prev = Rc::downgrade(&other_tail);
+                *tail.borrow_mut().next = Some(other_head);
+                *other_tail.borrow_mut().next = Some(head.clone());
+            }
+            None => self.head = Some(other_head),
+        }
+        self.tail = Some(other_tail);
+        self.len += std::mem::take(&mut other.len);
+
+        let moved = std::mem::take(&mut other.owner);
+        *moved.forward.borrow_mut() = Some(self.owner.clone());
+
+        self.reshaped();
+        other.reshaped();
+    }
+
+    /// Splits the list in two at `index`.
+    ///
+    /// This list keeps the elements before `index`,
+    /// the rest, from `index` up to the tail, is returned as a new list.
+    ///
+    /// ## Expensive
+    /// Walks to `index` and retags every moved node,
+    /// unless `index` is `0` and everything moves at once.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut ring: CircularLinkedList<_> = (1..=6).collect();
+    /// let team = ring.split_off(4);
+    /// assert_eq!(ring, CircularLinkedList::from([1, 2, 3, 4]));
+    /// assert_eq!(team, CircularLinkedList::from([5, 6]));
+    /// assert_eq!(team.iter().map_copied().take(3).collect::<Vec<_>>(), [5, 6, 5]);
+    ///
+    /// let single = CircularLinkedList::from([1, 2]).split_off(1);
+    /// assert_eq!(single.iter().map_copied().take(3).collect::<Vec<_>>(), [2, 2, 2]);
+    ///
+    /// // Moving everything over and over keeps handles cheap to check.
+    /// let mut list = CircularLinkedList::from([1, 2, 3]);
+    /// let two = list.handle_at(1).unwrap();
+    /// for _ in 0..1_000_000 {
+    ///     list = list.split_off(0);
+    ///     assert!(list.contains_node(&two));
+    /// }
+    /// drop(two);
+    /// drop(list);
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if `index` is greater than the length of the list.
+    pub fn split_off(&mut self, index: usize) -> Self {
+        assert!(index <= self.len, "split index out of bounds");
+
+        if index == 0 {
+            let mut rest = Self::new();
+            rest.append(self);
+            return rest;
+        }
+
+        let mut cursor = self.cursor_mut();
+        for _ in 1..index {
+            cursor.move_next();
+        }
+        cursor.split_after()
+    }
+
+    /// Splits the list in two right before the element `handle` refers to,
+    /// returning the part that starts there and goes up to the tail.
+    ///
+    /// Returns `None` if `handle` isn't part of this list.
+    ///
+    /// ## Expensive
+    /// See [`split_off`](Self::split_off).
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut ring = CircularLinkedList::from(["ann", "bob", "cat", "dan"]);
+    /// let cat = ring.handle_at(2).unwrap();
+    /// let team = ring.split_at_node(&cat).unwrap();
+    /// assert_eq!(ring, CircularLinkedList::from(["ann", "bob"]));
+    /// assert_eq!(team, CircularLinkedList::from(["cat", "dan"]));
+    /// assert_eq!(*team.get(&cat).unwrap(), "cat");
+    /// assert!(ring.split_at_node(&cat).is_none());
+    /// ```
+    pub fn split_at_node(&mut self, handle: &NodeHandle<T>) -> Option<Self> {
+        let node = self.resolve(handle)?;
+        let index = self
+            .iter_once()
+            .position(|n| Rc::ptr_eq(&n, &node))
+            .unwrap();
+        Some(self.split_off(index))
+    }
+
+    /// Replaces the elements in `range` with the ones from `replace_with`,
+    /// returning an iterator over the removed elements.
+    ///
+    /// Unlike [`Vec::splice`], the replacement happens right away,
+    /// not when the returned iterator is dropped.
+    ///
+    /// ## Expensive
+    /// See [`split_off`](Self::split_off).
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut cll = CircularLinkedList::from([1, 2, 3, 4, 5]);
+    /// let removed: Vec<_> = cll.splice(1..3, [20, 30, 35]).collect();
+    /// assert_eq!(removed, [2, 3]);
+    /// assert_eq!(cll, CircularLinkedList::from([1, 20, 30, 35, 4, 5]));
+    ///
+    /// cll.splice(.., [7]);
+    /// assert_eq!(cll.iter().map_copied().take(2).collect::<Vec<_>>(), [7, 7]);
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if the range starts after it ends, or ends after the tail.
+    pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> IntoIter<T>
     where
-        T: Copy,
+        R: RangeBounds<usize>,
+        I: IntoIterator<Item = T>,
     {
-        self.map(|x| x.borrow().value)
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end + 1,
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => self.len,
+        };
+        assert!(start <= end, "splice range starts after it ends");
+        assert!(end <= self.len, "splice range out of bounds");
+
+        let mut after = self.split_off(end);
+        let removed = self.split_off(start);
+        self.extend(replace_with);
+        self.append(&mut after);
+        removed.into_iter()
     }
 
-    /// Clones the inner value of each `Rc<RefCell<Node<T>>>`
+    /// Keeps only the elements for which `f` returns `true`, visiting them from the head.
     ///
-    /// Equivelent to `cll.map(|x| x.borrow().value.clone())`
-    pub fn map_cloned(self) -> impl Iterator<Item = T>
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut cll: CircularLinkedList<_> = (1..=6).collect();
+    /// cll.retain(|&x| x % 3 != 0);
+    /// assert_eq!(cll, CircularLinkedList::from([1, 2, 4, 5]));
+    /// assert_eq!(cll.iter().map_copied().nth(4), Some(1));
+    ///
+    /// cll.retain(|_| false);
+    /// assert!(cll.is_empty());
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if any element is borrowed.
+    pub fn retain<F>(&mut self, mut f: F)
     where
-        T: Clone,
+        F: FnMut(&T) -> bool,
     {
-        self.map(|x| x.borrow().value.clone())
+        self.retain_mut(|x| f(x));
     }
-}
 
-impl<T> Iterator for CllIter<T> {
-    type Item = Rcrfn<T>;
+    /// Like [`retain`](Self::retain), but `f` can also change the elements it keeps.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut cll = CircularLinkedList::from([1, 2, 3, 4]);
+    /// cll.retain_mut(|x| {
+    ///     *x *= 10;
+    ///     *x != 20
+    /// });
+    /// assert_eq!(cll, CircularLinkedList::from([10, 30, 40]));
+    /// ```
+    pub fn retain_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        let Some(mut prev) = self.tail.clone() else {
+            return;
+        };
 
-    fn next(&mut self) -> Pointer<T> {
-        let r = self.cursor.take()?;
+        for _ in 0..self.len {
+            let node = prev.borrow().next.clone().unwrap();
+            let keep = f(&mut node.borrow_mut().value);
+            if keep {
+                prev = node;
+            } else {
+                drop(node);
+                self.unlink_after(&prev);
+            }
+        }
+    }
 
-        if !self.stop || !Rc::ptr_eq(&r, self.tail.as_ref().unwrap()) {
-            self.cursor = r.borrow().next.clone();
+    /// Creates an iterator that removes the elements for which `predicate` returns `true`,
+    /// visiting them from the head, and yields them.
+    ///
+    /// Elements after the point where the iterator is dropped are left alone,
+    /// and so are matching elements whose node is still referenced or borrowed elsewhere,
+    /// e.g. by a live [`CllIter`].
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut cll: CircularLinkedList<_> = (1..=7).collect();
+    /// let evens: Vec<_> = cll.extract_if(|x| *x % 2 == 0).collect();
+    /// assert_eq!(evens, [2, 4, 6]);
+    /// assert_eq!(cll, CircularLinkedList::from([1, 3, 5, 7]));
+    ///
+    /// let mut big = cll.extract_if(|x| *x > 2);
+    /// assert_eq!(big.next(), Some(3));
+    /// drop(big);
+    /// assert_eq!(cll, CircularLinkedList::from([1, 5, 7]));
+    ///
+    /// // The iterator holds on to the node it yields next, and to the tail.
+    /// let mut iter = cll.iter();
+    /// iter.next();
+    /// assert_eq!(cll.extract_if(|_| true).collect::<Vec<_>>(), [1]);
+    /// assert_eq!(cll, CircularLinkedList::from([5, 7]));
+    /// drop(iter);
+    ///
+    /// assert_eq!(cll.extract_if(|_| true).collect::<Vec<_>>(), [5, 7]);
+    /// assert!(cll.is_empty());
+    /// ```
+    pub fn extract_if<F>(&mut self, predicate: F) -> ExtractIf<'_, T, F>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        ExtractIf {
+            prev: self.tail.clone(),
+            remaining: self.len,
+            list: self,
+            predicate,
         }
+    }
 
-        Some(r)
+    /// Removes consecutive equal elements, keeping the first of each run.
+    ///
+    /// The tail and the head aren't compared, see [`dedup_cyclic`](Self::dedup_cyclic) for that.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut cll = CircularLinkedList::from([1, 1, 2, 2, 2, 1]);
+    /// cll.dedup();
+    /// assert_eq!(cll, CircularLinkedList::from([1, 2, 1]));
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if any element is borrowed.
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_by(|a, b| a == b);
     }
-}
 
-impl<T: std::fmt::Debug> std::fmt::Debug for CircularLinkedList<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
-        let mut l = f.debug_list();
-        for rcrfn in self.iter_once() {
-            l.entry(&rcrfn.borrow().value);
-        }
-        l.finish()
+    /// Removes consecutive elements that map to the same key, keeping the first of each run.
+    pub fn dedup_by_key<K, F>(&mut self, mut key: F)
+    where
+        K: PartialEq,
+        F: FnMut(&mut T) -> K,
+    {
+        self.dedup_by(|a, b| key(a) == key(b));
     }
-}
 
-impl<T> std::iter::FromIterator<T> for CircularLinkedList<T> {
-    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
-        iter.into_iter().fold(Self::new(), |mut cll, x| {
-            cll.push(x);
-            cll
-        })
+    /// Removes consecutive elements `same_bucket` considers equal, keeping the first of each run.
+    ///
+    /// `same_bucket` gets the element that might be removed first,
+    /// then the one before it that is kept, like in [`Vec::dedup_by`].
+    pub fn dedup_by<F>(&mut self, same_bucket: F)
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        self.dedup_impl(false, same_bucket);
     }
-}
+
+    /// Like [`dedup`](Self::dedup), but also removes tail elements equal to the head,
+    /// so no two neighbours around the circle are equal.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut cll = CircularLinkedList::from([1, 2, 1, 1]);
+    /// cll.dedup_cyclic();
+    /// assert_eq!(cll, CircularLinkedList::from([1, 2]));
+    ///
+    /// let mut cll = CircularLinkedList::from([7, 7, 7]);
+    /// cll.dedup_cyclic();
+    /// assert_eq!(cll.iter().map_copied().take(2).collect::<Vec<_>>(), [7, 7]);
+    /// assert_eq!(cll.len(), 1);
+    /// ```
+    pub fn dedup_cyclic(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_by_cyclic(|a, b| a == b);
+    }
+
+    /// Like [`dedup_by_key`](Self::dedup_by_key),
+    /// but also compares the tail with the head, see [`dedup_cyclic`](Self::dedup_cyclic).
+    pub fn dedup_by_key_cyclic<K, F>(&mut self, mut key: F)
+    where
+        K: PartialEq,
+        F: FnMut(&mut T) -> K,
+    {
+        self.dedup_by_cyclic(|a, b| key(a) == key(b));
+    }
+
+    /// Like [`dedup_by`](Self::dedup_by),
+    /// but also compares the tail with the head, see [`dedup_cyclic`](Self::dedup_cyclic).
+    pub fn dedup_by_cyclic<F>(&mut self, same_bucket: F)
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        self.dedup_impl(true, same_bucket);
+    }
+
+    fn dedup_impl<F>(&mut self, cyclic: bool, mut same_bucket: F)
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        let Some(mut prev) = self.head.clone() else {
+            return;
+        };
+
+        for _ in 1..self.len {
+            let node = prev.borrow().next.clone().unwrap();
+            let same = same_bucket(&mut node.borrow_mut().value, &mut prev.borrow_mut().value);
+            if same {
+                drop(node);
+                self.unlink_after(&prev);
+            } else {
+                prev = node;
+            }
+        }
+        drop(prev);
+
+        while cyclic && self.len > 1 {
+            let (head, tail) = (self.head.clone().unwrap(), self.tail.clone().unwrap());
+            if !same_bucket(&mut tail.borrow_mut().value, &mut head.borrow_mut().value) {
+                break;
+            }
+            let before_tail = tail.borrow().prev.upgrade().unwrap();
+            drop((head, tail));
+            self.unlink_after(&before_tail);
+        }
+    }
+
+    /// Pushes an element after the tail and returns a handle to it.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut pool = CircularLinkedList::new();
+    /// pool.push("conn-1");
+    /// let conn = pool.push_handle("conn-2");
+    /// pool.push("conn-3");
+    ///
+    /// assert_eq!(*pool.get(&conn).unwrap(), "conn-2");
+    /// pool.insert_after_node(&conn, "conn-2b");
+    /// assert_eq!(pool.remove_node(&conn), Some("conn-2"));
+    /// assert!(pool.get(&conn).is_none());
+    /// assert_eq!(format!("{pool:?}"), r#"["conn-1", "conn-2b", "conn-3"]"#);
+    /// ```
+    pub fn push_handle(&mut self, value: T) -> NodeHandle<T> {
+        self.push(value);
+        NodeHandle::new(self.tail.as_ref().unwrap())
+    }
+
+    /// Pushes an element in front of the head and returns a handle to it.
+    pub fn push_front_handle(&mut self, value: T) -> NodeHandle<T> {
+        self.push_front(value);
+        NodeHandle::new(self.head.as_ref().unwrap())
+    }
+
+    /// Returns a handle to the element at `index`,
+    /// or `None` if `index` is out of bounds.
+    pub fn handle_at(&self, index: usize) -> Option<NodeHandle<T>> {
+        self.node_at(index).map(|node| NodeHandle::new(&node))
+    }
+
+    /// Whether `handle` refers to an element of this list.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut a = CircularLinkedList::from([1, 2]);
+    /// let b = CircularLinkedList::from([1, 2]);
+    /// let handle = a.handle_at(1).unwrap();
+    /// assert!(a.contains_node(&handle));
+    /// assert!(!b.contains_node(&handle));
+    /// a.pop_back();
+    /// assert!(!a.contains_node(&handle));
+    /// ```
+    pub fn contains_node(&self, handle: &NodeHandle<T>) -> bool {
+        !matches!(self.try_resolve(handle), Err(CllError::ForeignNode))
+    }
+
+    /// Borrows the element at `index`, which is either a position or a [`NodeHandle`].
+    ///
+    /// Positions wrap around the circle, so `len` is the head again,
+    /// and `None` is only returned if the list is empty.
+    /// A handle gives `None` if it is no longer part of this list.
+    ///
+    /// Indexing with `cll[index]` works the same, but gives an [`Element`] to borrow from.
+    ///
+    /// ## Expensive
+    /// The first lookup after the list has been modified walks the whole list,
+    /// and handles are looked up by searching it.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut cll = CircularLinkedList::from(['a', 'b', 'c']);
+    /// assert_eq!(*cll.get(1).unwrap(), 'b');
+    /// assert_eq!(*cll.get(3).unwrap(), 'a');
+    ///
+    /// let handle = cll.handle_at(2).unwrap();
+    /// assert_eq!(*cll.get(&handle).unwrap(), 'c');
+    ///
+    /// cll.clear();
+    /// assert!(cll.get(0).is_none());
+    /// ```
+    ///
+    /// The borrow stays valid even if the nodes around it are relinked through a [`CllIter`]:
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let a = CircularLinkedList::from([1, 2, 3]);
+    /// let b = CircularLinkedList::from([4, 5]);
+    /// let two = a.get(1).unwrap();
+    ///
+    /// let (node_a, node_b) = (a.iter().next().unwrap(), b.iter().next().unwrap());
+    /// std::mem::swap(&mut *node_a.borrow_mut(), &mut *node_b.borrow_mut());
+    /// drop((node_a, node_b, b));
+    /// assert_eq!(*two, 2);
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if the element is mutably borrowed.
+    /// See [`try_get`](Self::try_get) for a non-panicking version.
+    pub fn get<I: CllIndex<T>>(&self, index: I) -> Option<Ref<'_, T>> {
+        found(self.try_get(index))
+    }
+
+    /// Like [`get`](Self::get), but reports every failure as a [`CllError`].
+    ///
+    /// Walking the list after it has been modified fails if any element is mutably borrowed.
+    pub fn try_get<I: CllIndex<T>>(&self, index: I) -> Result<Ref<'_, T>, CllError> {
+        index.try_element(self)?.try_borrow()
+    }
+
+    /// Mutably borrows the element at `index`, see [`get`](Self::get).
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut cll = CircularLinkedList::from([1, 2, 3]);
+    /// *cll.get_mut(4).unwrap() *= 10;
+    /// assert_eq!(cll, CircularLinkedList::from([1, 20, 3]));
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if the element is borrowed.
+    /// See [`try_get_mut`](Self::try_get_mut) for a non-panicking version.
+    pub fn get_mut<I: CllIndex<T>>(&mut self, index: I) -> Option<RefMut<'_, T>> {
+        found(self.try_get_mut(index))
+    }
+
+    /// Like [`get_mut`](Self::get_mut), but reports every failure as a [`CllError`].
+    pub fn try_get_mut<I: CllIndex<T>>(&mut self, index: I) -> Result<RefMut<'_, T>, CllError> {
+        index.try_element(self)?.try_borrow_mut()
+    }
+
+    /// Borrows the element at `index`, counting backward from the head if it's negative,
+    /// so `-1` is the tail.
+    ///
+    /// Like [`get`](Self::get), the index wraps around the circle.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let cll = CircularLinkedList::from([1, 2, 3]);
+    /// assert_eq!(*cll.get_wrapping(-1).unwrap(), 3);
+    /// assert_eq!(*cll.get_wrapping(-5).unwrap(), 2);
+    /// assert_eq!(*cll.get_wrapping(4).unwrap(), 2);
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if the element is mutably borrowed.
+    pub fn get_wrapping(&self, index: isize) -> Option<Ref<'_, T>> {
+        self.get(self.wrap(index)?)
+    }
+
+    /// Mutably borrows the element at `index`, see [`get_wrapping`](Self::get_wrapping).
+    ///
+    /// # Panics
+    /// Panics if the element is borrowed.
+    pub fn get_wrapping_mut(&mut self, index: isize) -> Option<RefMut<'_, T>> {
+        let index = self.wrap(index)?;
+        self.get_mut(index)
+    }
+
+    /// Turns a possibly negative index into a position, or `None` if the list is empty.
+    fn wrap(&self, index: isize) -> Option<usize> {
+        let len = self.len.try_into().ok().filter(|&len: &isize| len > 0)?;
+        Some(index.rem_euclid(len) as usize)
+    }
+
+    /// Inserts an element right after the one `handle` refers to and returns a handle to it.
+    ///
+    /// Returns `None`, dropping `value`, if `handle` is no longer part of this list.
+    pub fn insert_after_node(&mut self, handle: &NodeHandle<T>, value: T) -> Option<NodeHandle<T>> {
+        let node = self.resolve(handle)?;
+        let next = Self::link_after(&node, value);
+        self.len += 1;
+        if Rc::ptr_eq(&node, self.tail.as_ref().unwrap()) {
+            self.tail = Some(next.clone());
+        }
+        self.reshaped();
+        Some(NodeHandle::new(&next))
+    }
+
+    /// Removes the element `handle` refers to and returns it,
+    /// or `None` if it is no longer part of this list.
+    ///
+    /// Runs in constant time, every node remembers the one linking to it.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut cll = CircularLinkedList::from([3, 1, 2]);
+    /// let handles: Vec<_> = (0..3).map(|i| cll.handle_at(i).unwrap()).collect();
+    /// cll.sort();
+    /// cll.append(&mut CircularLinkedList::from([4, 5]));
+    /// cll.reverse();
+    /// assert_eq!(cll.remove_node(&handles[2]), Some(2));
+    /// assert_eq!(cll.remove_node(&handles[1]), Some(1));
+    /// assert_eq!(cll, CircularLinkedList::from([5, 4, 3]));
+    /// assert_eq!(cll.remove_node(&handles[0]), Some(3));
+    /// assert_eq!(cll.iter().map_copied().nth(2), Some(5));
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if the node is still referenced elsewhere, e.g. by a live [`CllIter`],
+    /// or if it or its neighbours are borrowed.
+    /// See [`try_remove_node`](Self::try_remove_node) for a non-panicking version.
+    pub fn remove_node(&mut self, handle: &NodeHandle<T>) -> Option<T> {
+        found(self.try_remove_node(handle))
+    }
+
+    /// Like [`remove_node`](Self::remove_node), but reports every failure as a [`CllError`].
+    ///
+    /// The list is left untouched on failure.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut a = CircularLinkedList::from([1, 2, 3]);
+    /// let mut b = CircularLinkedList::from([1, 2, 3]);
+    /// let handle = a.handle_at(1).unwrap();
+    /// assert_eq!(b.try_remove_node(&handle), Err(CllError::ForeignNode));
+    /// assert_eq!(a.try_remove_node(&handle), Ok(2));
+    /// assert_eq!(a.try_remove_node(&handle), Err(CllError::ForeignNode));
+    /// ```
+    pub fn try_remove_node(&mut self, handle: &NodeHandle<T>) -> Result<T, CllError> {
+        let removed = {
+            let node = self.try_resolve(handle)?;
+            let prev = node.try_borrow()?.prev.upgrade().unwrap();
+            drop(node);
+            self.check_unlink_after(&prev)?;
+            self.unlink_after(&prev)
+        };
+        Ok(Self::into_value(removed))
+    }
+
+    /// Upgrades `handle`, checking the node still belongs to this list.
+    fn resolve(&self, handle: &NodeHandle<T>) -> Pointer<T> {
+        found(self.try_resolve(handle))
+    }
+
+    fn try_resolve(&self, handle: &NodeHandle<T>) -> Result<Rcrfn<T>, CllError> {
+        let node = handle.node.upgrade().ok_or(CllError::ForeignNode)?;
+        let owner = node.try_borrow()?.owner.as_ref().map(Owner::root);
+        match owner {
+            Some(owner) if Rc::ptr_eq(&owner, &self.owner) => Ok(node),
+            _ => Err(CllError::ForeignNode),
+        }
+    }
+
+    /// Whether the list holds an element equal to `x`.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let cll = CircularLinkedList::from(["Mike", "Hank", "Gus"]);
+    /// assert!(cll.contains(&"Gus"));
+    /// assert!(!cll.contains(&"Walt"));
+    /// ```
+    pub fn contains(&self, x: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter_values_once().any(|value| *value == *x)
+    }
+
+    /// Index of the first element, from the head, matching `predicate`.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let cll = CircularLinkedList::from([1, 4, 2, 4]);
+    /// assert_eq!(cll.position(|&x| x == 4), Some(1));
+    /// assert_eq!(cll.rposition(|&x| x == 4), Some(3));
+    /// assert_eq!(cll.position(|&x| x == 3), None);
+    /// ```
+    pub fn position<P>(&self, mut predicate: P) -> Option<usize>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter_values_once().position(|value| predicate(&value))
+    }
+
+    /// Index of the last element, up to the tail, matching `predicate`.
+    ///
+    /// Checks the elements backward from the tail, stopping at the first match.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let cll = CircularLinkedList::from([1, 2, 3, 2, 1]);
+    /// assert_eq!(cll.rposition(|&x| x == 2), Some(3));
+    /// assert_eq!(cll.rposition(|&x| x == 4), None);
+    /// ```
+    pub fn rposition<P>(&self, mut predicate: P) -> Option<usize>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.reversed_iter_once()
+            .position(|node| predicate(&node.borrow().value))
+            .map(|i| self.len - 1 - i)
+    }
+
+    /// Borrows the first element, from the head, matching `predicate`.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let cll = CircularLinkedList::from(["Mike", "Hank", "Gus"]);
+    /// assert_eq!(*cll.find(|name| name.len() == 4).unwrap(), "Mike");
+    /// assert_eq!(cll.find_map(|name| name.strip_prefix('G')), Some("us"));
+    /// ```
+    pub fn find<P>(&self, mut predicate: P) -> Option<Ref<'_, T>>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter_values_once().find(|value| predicate(value))
+    }
+
+    /// Applies `f` to the elements from the head and returns the first `Some` result.
+    pub fn find_map<B, F>(&self, mut f: F) -> Option<B>
+    where
+        F: FnMut(&T) -> Option<B>,
+    {
+        self.iter_values_once().find_map(|value| f(&value))
+    }
+
+    /// Returns a handle to the first element, from the head, matching `predicate`.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut cll = CircularLinkedList::from([1, 2, 3]);
+    /// let two = cll.find_node(|&x| x == 2).unwrap();
+    /// assert_eq!(cll.remove_node(&two), Some(2));
+    /// ```
+    pub fn find_node<P>(&self, mut predicate: P) -> Option<NodeHandle<T>>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter_once()
+            .find(|node| predicate(&node.borrow().value))
+            .map(|node| NodeHandle::new(&node))
+    }
+
+    /// Like [`position`](Self::position), but starts searching at `offset`
+    /// and wraps around the circle, checking every element exactly once.
+    ///
+    /// `offset` wraps around too, and the index returned counts from the head.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let cll = CircularLinkedList::from([4, 1, 4, 2]);
+    /// assert_eq!(cll.position_from(1, |&x| x == 4), Some(2));
+    /// assert_eq!(cll.position_from(3, |&x| x == 4), Some(0));
+    /// assert_eq!(cll.position_from(7, |&x| x == 1), Some(1));
+    /// assert_eq!(cll.position_from(1, |&x| x == 3), None);
+    /// ```
+    pub fn position_from<P>(&self, offset: usize, mut predicate: P) -> Option<usize>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let len = self.len;
+        let offset = offset.checked_rem(len)?;
+        let i = self
+            .values_from(offset)
+            .position(|value| predicate(&value))?;
+        Some((offset + i) % len)
+    }
+
+    /// Like [`find`](Self::find), but starts searching at `offset`,
+    /// see [`position_from`](Self::position_from).
+    pub fn find_from<P>(&self, offset: usize, mut predicate: P) -> Option<Ref<'_, T>>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let offset = offset.checked_rem(self.len)?;
+        self.values_from(offset).find(|value| predicate(value))
+    }
+
+    /// Every value once, starting at `offset`, which must be less than `len`.
+    fn values_from(&self, offset: usize) -> std::iter::Take<std::iter::Skip<Values<'_, T>>> {
+        self.iter_values().skip(offset).take(self.len)
+    }
+
+    /// Moves the head `n` nodes forward along the circle,
+    /// so the element at index `n` becomes the new head.
+    ///
+    /// No nodes are reallocated or relinked, only `head` and `tail` move.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut cll: CircularLinkedList<_> = (1..=4).collect();
+    /// cll.rotate_forward(1);
+    /// assert_eq!(format!("{cll:?}"), "[2, 3, 4, 1]");
+    /// cll.rotate_forward(6);
+    /// assert_eq!(format!("{cll:?}"), "[4, 1, 2, 3]");
+    /// ```
+    pub fn rotate_forward(&mut self, n: usize) {
+        let len = self.len();
+        if len == 0 {
+            return;
+        }
+
+        if n.is_multiple_of(len) {
+            return;
+        }
+
+        for _ in 0..n % len {
+            let next = self.head.as_ref().unwrap().borrow().next.clone();
+            self.tail = std::mem::replace(&mut self.head, next);
+        }
+        self.reshaped();
+    }
+
+    /// Moves the head `n` nodes backward along the circle,
+    /// so the current tail becomes the head when `n` is `1`.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut cll: CircularLinkedList<_> = (1..=4).collect();
+    /// cll.rotate_backward(1);
+    /// assert_eq!(format!("{cll:?}"), "[4, 1, 2, 3]");
+    /// ```
+    pub fn rotate_backward(&mut self, n: usize) {
+        let len = self.len();
+        if len == 0 {
+            return;
+        }
+
+        if n.is_multiple_of(len) {
+            return;
+        }
+
+        for _ in 0..n % len {
+            let prev = self.tail.as_ref().unwrap().borrow().prev.upgrade();
+            self.head = std::mem::replace(&mut self.tail, prev);
+        }
+        self.reshaped();
+    }
+
+    /// Rotates the list so that the first element matching `predicate`
+    /// becomes the head.
+    ///
+    /// Returns `false` and leaves the list untouched if no element matches.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut cll: CircularLinkedList<_> = ["Mike", "Hank", "Gus"].into_iter().collect();
+    /// assert!(cll.rotate_to(|name| name.starts_with('G')));
+    /// assert_eq!(format!("{cll:?}"), r#"["Gus", "Mike", "Hank"]"#);
+    /// assert!(!cll.rotate_to(|name| name.is_empty()));
+    /// ```
+    pub fn rotate_to<F>(&mut self, mut predicate: F) -> bool
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let Some(n) = self.iter_once().position(|x| predicate(&x.borrow().value)) else {
+            return false;
+        };
+        self.rotate_forward(n);
+        true
+    }
+
+    /// Whether `other` holds the same elements in the same cyclic order,
+    /// regardless of where either list's head is.
+    ///
+    /// Runs in linear time.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let cll = CircularLinkedList::from([1, 2, 3]);
+    /// assert!(cll.eq_up_to_rotation(&CircularLinkedList::from([2, 3, 1])));
+    /// assert!(!cll.eq_up_to_rotation(&CircularLinkedList::from([3, 2, 1])));
+    /// ```
+    pub fn eq_up_to_rotation(&self, other: &Self) -> bool
+    where
+        T: PartialEq,
+    {
+        let a: Vec<_> = self.iter_values_once().collect();
+        let b: Vec<_> = other.iter_values_once().collect();
+        is_rotation(&a, b.iter())
+    }
+
+    /// Like [`eq_up_to_rotation`](Self::eq_up_to_rotation),
+    /// but also accepts `other` running around the circle in the opposite direction.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let cll = CircularLinkedList::from([1, 2, 3, 4]);
+    /// assert!(cll.eq_up_to_rotation_and_reflection(&CircularLinkedList::from([2, 1, 4, 3])));
+    /// assert!(!cll.eq_up_to_rotation_and_reflection(&CircularLinkedList::from([1, 3, 2, 4])));
+    /// ```
+    pub fn eq_up_to_rotation_and_reflection(&self, other: &Self) -> bool
+    where
+        T: PartialEq,
+    {
+        let a: Vec<_> = self.iter_values_once().collect();
+        let b: Vec<_> = other.iter_values_once().collect();
+        is_rotation(&a, b.iter()) || is_rotation(&a, b.iter().rev())
+    }
+
+    /// Rotates the list so that it starts at its lexicographically smallest rotation.
+    ///
+    /// Lists that are [equal up to rotation](Self::eq_up_to_rotation)
+    /// are equal after being canonicalized,
+    /// which makes the canonical form suitable for hashing and deduplication.
+    ///
+    /// Runs in linear time, using Booth's algorithm.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut cll = CircularLinkedList::from([3, 1, 2, 1, 1]);
+    /// cll.canonicalize();
+    /// assert_eq!(cll, CircularLinkedList::from([1, 1, 3, 1, 2]));
+    /// ```
+    pub fn canonicalize(&mut self)
+    where
+        T: Ord,
+    {
+        let values: Vec<_> = self.iter_values_once().collect();
+        let n = least_rotation(&values);
+        drop(values);
+        self.rotate_forward(n);
+    }
+
+    /// Reverses the order of the elements in place, so the tail becomes the head.
+    ///
+    /// Every node is relinked to point at the one before it, no values are moved.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut cll: CircularLinkedList<_> = (1..=4).collect();
+    /// cll.reverse();
+    /// assert_eq!(cll, CircularLinkedList::from([4, 3, 2, 1]));
+    /// assert_eq!(cll.iter().map_copied().nth(4), Some(4));
+    /// ```
+    pub fn reverse(&mut self) {
+        let Some(mut prev) = self.tail.clone() else {
+            return;
+        };
+
+        let mut node = self.head.clone().unwrap();
+        for _ in 0..self.len {
+            let mut node_ref = node.borrow_mut();
+            let next = node_ref.next.replace(prev).unwrap();
+            node_ref.prev = Rc::downgrade(&next);
+            drop(node_ref);
+            prev = std::mem::replace(&mut node, next);
+        }
+        std::mem::swap(&mut self.head, &mut self.tail);
+        self.reshaped();
+    }
+
+    /// Sorts the list in place, keeping equal elements in their original order,
+    /// then makes the smallest element the head.
+    ///
+    /// Nodes are relinked with a bottom-up merge sort, so no values are moved or reallocated.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut cll = CircularLinkedList::from([5, 1, 4, 2, 3]);
+    /// cll.sort();
+    /// assert_eq!(cll, CircularLinkedList::from([1, 2, 3, 4, 5]));
+    /// assert_eq!(cll.iter().map_copied().nth(5), Some(1));
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if any element is borrowed, leaving the list untouched.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// use std::panic::{catch_unwind, AssertUnwindSafe};
+    /// let mut cll = CircularLinkedList::from([5, 4, 3, 2, 1]);
+    /// let third = cll.iter().nth(2).unwrap();
+    /// let guard = third.borrow();
+    /// assert!(catch_unwind(AssertUnwindSafe(|| cll.sort())).is_err());
+    /// drop(guard);
+    /// assert_eq!(cll, CircularLinkedList::from([5, 4, 3, 2, 1]));
+    /// ```
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(T::cmp);
+    }
+
+    /// Sorts the list in place with a comparator function, see [`sort`](Self::sort).
+    ///
+    /// If `compare` panics, the list keeps every element, in an unspecified order.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut cll = CircularLinkedList::from([(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd')]);
+    /// cll.sort_by(|a, b| a.0.cmp(&b.0));
+    /// assert_eq!(format!("{cll:?}"), "[(1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')]");
+    ///
+    /// use std::panic::{catch_unwind, AssertUnwindSafe};
+    /// let mut cll: CircularLinkedList<_> = (1..=10).rev().collect();
+    /// let mut calls = 0;
+    /// let sorted = catch_unwind(AssertUnwindSafe(|| {
+    ///     cll.sort_by(|a, b| {
+    ///         calls += 1;
+    ///         assert!(calls < 8, "gave up");
+    ///         a.cmp(b)
+    ///     })
+    /// }));
+    /// assert!(sorted.is_err());
+    /// assert_eq!(cll.len(), 10);
+    /// cll.sort();
+    /// assert_eq!(cll, (1..=10).collect());
+    /// assert_eq!(cll.pop_back(), Some(10));
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if any element is borrowed, leaving the list untouched.
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        // Relinking a borrowed node would panic with the list taken apart.
+        for node in self.iter_once() {
+            if let Err(e) = node.try_borrow_mut() {
+                panic!("{}", CllError::from(e));
+            }
+        }
+
+        let len = std::mem::take(&mut self.len);
+        let (Some(head), Some(tail)) = (self.head.take(), self.tail.take()) else {
+            return;
+        };
+        *tail.borrow_mut().next = None;
+
+        let mut guard = SortGuard {
+            list: self,
+            len,
+            unsorted: Some((head, tail)),
+            bins: Vec::new(),
+            merged: None,
+            earlier: None,
+            later: None,
+        };
+        while let Some((node, last)) = guard.unsorted.take() {
+            if let Some(next) = node.borrow_mut().next.take() {
+                guard.unsorted = Some((next, last));
+            }
+            let mut run = Some((node.clone(), node));
+            for i in 0..guard.bins.len() {
+                let later = run.take().unwrap();
+                match guard.bins[i].take() {
+                    Some(earlier) => run = Some(guard.merge(earlier, later, &mut compare)),
+                    None => {
+                        guard.bins[i] = Some(later);
+                        break;
+                    }
+                }
+            }
+            if run.is_some() {
+                guard.bins.push(run);
+            }
+        }
+
+        let mut sorted = None;
+        for i in 0..guard.bins.len() {
+            if let Some(earlier) = guard.bins[i].take() {
+                sorted = Some(match sorted {
+                    Some(later) => guard.merge(earlier, later, &mut compare),
+                    None => earlier,
+                });
+            }
+        }
+        guard.bins.push(sorted);
+    }
+
+    /// Sorts the list in place by the key `f` extracts, see [`sort`](Self::sort).
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut cll = CircularLinkedList::from(["ccc", "a", "bb"]);
+    /// cll.sort_by_key(|s| s.len());
+    /// assert_eq!(cll, CircularLinkedList::from(["a", "bb", "ccc"]));
+    /// ```
+    pub fn sort_by_key<K, F>(&mut self, mut f: F)
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        self.sort_by(|a, b| f(a).cmp(&f(b)));
+    }
+
+    /// Sorts the list in place with a comparator function,
+    /// without promising to keep equal elements in their original order.
+    ///
+    /// Currently the same as [`sort_by`](Self::sort_by).
+    pub fn sort_unstable_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.sort_by(compare);
+    }
+
+    /// Whether some rotation of the list is sorted,
+    /// i.e. there's at most one place, counting from the tail to the head,
+    /// where an element is followed by a smaller or incomparable one.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// assert!(CircularLinkedList::from([3, 4, 1, 2]).is_sorted_cyclic());
+    /// assert!(CircularLinkedList::from([1, 1, 1]).is_sorted_cyclic());
+    /// assert!(!CircularLinkedList::from([1, 3, 2]).is_sorted_cyclic());
+    /// assert!(!CircularLinkedList::from([1.0, f64::NAN]).is_sorted_cyclic());
+    /// ```
+    pub fn is_sorted_cyclic(&self) -> bool
+    where
+        T: PartialOrd,
+    {
+        let descents = self
+            .iter_values_once()
+            .zip(self.iter_values().skip(1))
+            .filter(|(a, b)| {
+                !matches!((**a).partial_cmp(b), Some(Ordering::Less | Ordering::Equal))
+            })
+            .count();
+        descents <= 1
+    }
+
+    fn node_at(&self, index: usize) -> Pointer<T> {
+        self.iter_once().nth(index)
+    }
+
+    /// Like `node_at`, but reports borrowed nodes instead of panicking.
+    fn try_node_at(&self, index: usize) -> Result<Rcrfn<T>, CllError> {
+        if index >= self.len {
+            return Err(CllError::OutOfBounds);
+        }
+
+        let mut node = self.head.clone().unwrap();
+        for _ in 0..index {
+            let next = node.try_borrow()?.next.clone().unwrap();
+            node = next;
+        }
+        Ok(node)
+    }
+
+    /// Checks that `unlink_after(prev)` won't panic and that the unlinked node's
+    /// value can be moved out, assuming the caller holds no other clone than `prev`.
+    fn check_unlink_after(&self, prev: &Rcrfn<T>) -> Result<(), CllError> {
+        let node = prev.try_borrow()?.next.clone().unwrap();
+        let next = node.try_borrow()?.next.clone().unwrap();
+        prev.try_borrow_mut()?;
+        node.try_borrow_mut()?;
+        // The node after it is relinked to `prev`.
+        next.try_borrow_mut()?;
+        drop(next);
+
+        let is =
+            |end: Option<&Rcrfn<T>>| usize::from(end.is_some_and(|end| Rc::ptr_eq(&node, end)));
+        let cached = usize::from(self.nodes.get().is_some());
+        // `prev.next`, `head`, `tail` and `nodes` belong to the list, `node` and `prev` to us.
+        let owned = 1 + is(self.head.as_ref()) + is(self.tail.as_ref()) + cached;
+        let held = 1 + usize::from(Rc::ptr_eq(&node, prev));
+        if Rc::strong_count(&node) > owned + held {
+            return Err(CllError::NodeInUse);
+        }
+        Ok(())
+    }
+
+    /// Unlinks the node that follows `prev` and returns it,
+    /// keeping `head` and `tail` pointing into the circle.
+    fn unlink_after(&mut self, prev: &Rcrfn<T>) -> Rcrfn<T> {
+        let node = prev.borrow().next.clone().unwrap();
+        let next = node.borrow().next.clone().unwrap();
+        // Borrow each node up front, so a panic leaves the list as it was.
+        for rcrfn in [prev, &node, &next] {
+            drop(rcrfn.borrow_mut());
+        }
+
+        let mut node_ref = node.borrow_mut();
+        node_ref.next.take();
+        node_ref.owner = None;
+        drop(node_ref);
+        self.len -= 1;
+
+        if Rc::ptr_eq(&node, &next) {
+            self.head = None;
+            self.tail = None;
+        } else {
+            *prev.borrow_mut().next = Some(next.clone());
+            next.borrow_mut().prev = Rc::downgrade(prev);
+            if Rc::ptr_eq(&node, self.head.as_ref().unwrap()) {
+                self.head = Some(next);
+            }
+            if Rc::ptr_eq(&node, self.tail.as_ref().unwrap()) {
+                self.tail = Some(prev.clone());
+            }
+        }
+        self.reshaped();
+        node
+    }
+
+    fn into_value(rcrfn: Rcrfn<T>) -> T {
+        match Rc::try_unwrap(rcrfn) {
+            Ok(cell) => cell.into_inner().value,
+            Err(_) => panic!("node is still referenced outside of the list"),
+        }
+    }
+
+    /// Creates an iterator that, by default,
+    /// will never end, unless the list is empty.
+    pub fn iter(&self) -> CllIter<T> {
+        self.iter_between(self.head.clone(), self.tail.clone())
+    }
+
+    /// Creates an iterator that, by default,
+    /// will iterate throught the list and stop at the tail element.
+    pub fn iter_once(&self) -> CllIter<T> {
+        self.iter().once()
+    }
+
+    /// Like [`iter`](Self::iter), but starts at the element at `index`,
+    /// which wraps around the circle.
+    ///
+    /// After [`once`](CllIter::once), it stops at the element before the one it started at,
+    /// or, in [`live`](CllIter::live) mode, at the tail once the list has been modified.
+    ///
+    /// ## Expensive
+    /// Walks from the head to `index`.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let cll: CircularLinkedList<_> = (1..=4).collect();
+    /// assert_eq!(cll.iter_from(2).map_copied().take(6).collect::<Vec<_>>(), [3, 4, 1, 2, 3, 4]);
+    /// assert_eq!(cll.iter_once_from(6).map_copied().collect::<Vec<_>>(), [3, 4, 1, 2]);
+    /// assert_eq!(cll.iter_once_from(6).len(), 4);
+    /// ```
+    pub fn iter_from(&self, index: usize) -> CllIter<T> {
+        let Some(offset) = index.checked_rem(self.len) else {
+            return self.iter();
+        };
+
+        let last = match offset {
+            0 => self.tail.clone(),
+            _ => self.node_at(offset - 1),
+        };
+        let first = last
+            .as_ref()
+            .map(|last| last.borrow().next.clone().unwrap());
+        self.iter_between(first, last)
+    }
+
+    /// Like [`iter_once`](Self::iter_once), but starts at the element at `index`,
+    /// see [`iter_from`](Self::iter_from).
+    pub fn iter_once_from(&self, index: usize) -> CllIter<T> {
+        self.iter_from(index).once()
+    }
+
+    /// Like [`iter`](Self::iter), but starts at the element `handle` refers to,
+    /// see [`iter_from`](Self::iter_from).
+    ///
+    /// Returns `None` if `handle` isn't part of this list.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut players = CircularLinkedList::from(["ann", "bob", "cat"]);
+    /// let bob = players.handle_at(1).unwrap();
+    /// let order: Vec<_> = players.iter_from_node(&bob).unwrap().once().map_copied().collect();
+    /// assert_eq!(order, ["bob", "cat", "ann"]);
+    ///
+    /// players.remove_node(&bob);
+    /// assert!(players.iter_from_node(&bob).is_none());
+    /// ```
+    pub fn iter_from_node(&self, handle: &NodeHandle<T>) -> Option<CllIter<T>> {
+        let node = self.resolve(handle)?;
+        let last = node.borrow().prev.upgrade();
+        Some(self.iter_between(Some(node), last))
+    }
+
+    /// Creates an iterator that starts at `first` and, once stopped, ends at `last`.
+    fn iter_between(&self, first: Pointer<T>, last: Pointer<T>) -> CllIter<T> {
+        CllIter {
+            cursor: first,
+            tail: last,
+            stop: false,
+            len: self.len,
+            pos: 0,
+            mode: Mode::Snapshot,
+            shape: self.shape.clone(),
+            generation: self.shape.generation.get(),
+        }
+    }
+
+    /// Creates an iterator that starts at the head and hops `k` nodes forward every step,
+    /// never ending unless the list is empty.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let players: CircularLinkedList<_> = (0..7).collect();
+    /// let every_third: Vec<_> = players.step(3).map(|p| p.borrow().value).take(8).collect();
+    /// assert_eq!(every_third, [0, 3, 6, 2, 5, 1, 4, 0]);
+    /// ```
+    pub fn step(&self, k: usize) -> Step<T> {
+        Step {
+            cursor: self.head.clone(),
+            hops: k.checked_rem(self.len).unwrap_or(0),
+            remaining: None,
+        }
+    }
+
+    /// Like [`step`](Self::step), but stops before coming back to the head,
+    /// so every element reachable by hopping `k` nodes is visited exactly once.
+    ///
+    /// That is `len / gcd(len, k)` elements.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let players: CircularLinkedList<_> = (0..6).collect();
+    /// let evens: Vec<_> = players.step_once(2).map(|p| p.borrow().value).collect();
+    /// assert_eq!(evens, [0, 2, 4]);
+    /// assert_eq!(players.step_once(5).len(), 6);
+    /// assert_eq!(players.step_once(6).len(), 1);
+    /// assert_eq!(CircularLinkedList::<i32>::new().step_once(2).len(), 0);
+    /// ```
+    pub fn step_once(&self, k: usize) -> Step<T> {
+        let mut step = self.step(k);
+        step.remaining = Some(self.len.checked_div(gcd(self.len, step.hops)).unwrap_or(0));
+        step
+    }
+
+    /// Creates an iterator that goes through the list once, from the tail back to the head,
+    /// following the links to the previous nodes.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let cll: CircularLinkedList<_> = (1..=3).collect();
+    /// let values: Vec<_> = cll.reversed_iter_once().map(|x| x.borrow().value).collect();
+    /// assert_eq!(values, [3, 2, 1]);
+    /// ```
+    pub fn reversed_iter_once(&self) -> ReversedIter<T> {
+        ReversedIter {
+            cursor: self.tail.clone(),
+            remaining: self.len,
+        }
+    }
+
+    /// Creates an iterator over borrowed values that,
+    /// like [`iter`](Self::iter), will never end unless the list is empty.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let cll: CircularLinkedList<_> = ["a", "b"].map(String::from).into_iter().collect();
+    /// let lens: Vec<_> = cll.iter_values().map(|s| s.len()).take(3).collect();
+    /// assert_eq!(lens, [1, 1, 1]);
+    /// ```
+    pub fn iter_values(&self) -> Values<'_, T> {
+        Values {
+            nodes: self.nodes(),
+            pos: 0,
+            remaining: None,
+        }
+    }
+
+    /// Creates an iterator over borrowed values that stops at the tail element.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let cll: CircularLinkedList<_> = ["Mike", "Hank"].map(String::from).into_iter().collect();
+    /// let joined = cll.iter_values_once().fold(String::new(), |acc, s| acc + &s);
+    /// assert_eq!(joined, "MikeHank");
+    /// ```
+    pub fn iter_values_once(&self) -> Values<'_, T> {
+        Values {
+            nodes: self.nodes(),
+            pos: 0,
+            remaining: Some(self.len),
+        }
+    }
+
+    /// Creates an iterator over mutably borrowed values that stops at the tail element.
+    ///
+    /// There is no never ending version, since a value can't be mutably borrowed twice.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut cll: CircularLinkedList<_> = ["Mike", "Hank"].map(String::from).into_iter().collect();
+    /// for mut name in cll.iter_values_mut() {
+    ///     name.push('!');
+    /// }
+    /// assert_eq!(format!("{cll:?}"), r#"["Mike!", "Hank!"]"#);
+    /// ```
+    pub fn iter_values_mut(&mut self) -> ValuesMut<'_, T> {
+        ValuesMut {
+            nodes: self.nodes().iter(),
+        }
+    }
+
+    /// Like [`iter_values_once`](Self::iter_values_once),
+    /// but yields an error instead of panicking when an element is mutably borrowed,
+    /// and stops there.
+    ///
+    /// Every element is checked up front unless the list has been walked already
+    /// since it was last modified, so a mutably borrowed element may be reported first.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let cll = CircularLinkedList::from([1, 2, 3]);
+    /// let second = cll.iter().nth(1).unwrap();
+    ///
+    /// let guard = second.borrow_mut();
+    /// let mut values = cll.try_iter_values();
+    /// assert!(matches!(values.next(), Some(Err(CllError::AlreadyBorrowed))));
+    /// assert!(values.next().is_none());
+    /// drop(guard);
+    ///
+    /// let mut values = cll.try_iter_values();
+    /// assert_eq!(values.next().map(|x| x.map(|x| *x)), Some(Ok(1)));
+    /// let guard = second.borrow_mut();
+    /// assert!(matches!(values.next(), Some(Err(CllError::AlreadyBorrowed))));
+    /// assert!(values.next().is_none());
+    /// # drop(guard);
+    /// ```
+    pub fn try_iter_values(&self) -> TryValues<'_, T> {
+        match self.try_nodes() {
+            Ok(nodes) => TryValues {
+                nodes: nodes.iter(),
+                error: None,
+            },
+            Err(error) => TryValues {
+                nodes: [].iter(),
+                error: Some(error),
+            },
+        }
+    }
+
+    /// Every node from the head to the tail, see the `nodes` field.
+    ///
+    /// # Panics
+    /// Panics if a node is mutably borrowed.
+    fn nodes(&self) -> &[Element<T>] {
+        self.try_nodes().unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    fn try_nodes(&self) -> Result<&[Element<T>], CllError> {
+        if let Some(nodes) = self.nodes.get() {
+            return Ok(nodes);
+        }
+
+        let mut nodes = Vec::with_capacity(self.len);
+        let mut cursor = self.head.clone();
+        for _ in 0..self.len {
+            let node = cursor.unwrap();
+            cursor = node.try_borrow()?.next.clone();
+            nodes.push(Element(node));
+        }
+        Ok(self.nodes.get_or_init(|| nodes))
+    }
+
+    /// Creates a cursor that starts at the head and can edit the list as it walks around it.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut cll = CircularLinkedList::from([1, 2, 3, 4]);
+    /// let mut cursor = cll.cursor_mut();
+    /// // Double every even element and drop every odd one, going around twice.
+    /// for _ in 0..8 {
+    ///     if *cursor.current().unwrap() % 2 == 0 {
+    ///         *cursor.current().unwrap() *= 2;
+    ///         cursor.move_next();
+    ///     } else {
+    ///         cursor.remove_current();
+    ///     }
+    /// }
+    /// assert_eq!(cll, CircularLinkedList::from([16, 32]));
+    /// ```
+    pub fn cursor_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            current: self.head.clone(),
+            prev: self.tail.clone(),
+            peeked: None,
+            index: 0,
+            list: self,
+        }
+    }
+}
+
+/// Whether `b` is a rotation of `a`, using Knuth-Morris-Pratt to search for `b` in `a` doubled.
+pub(crate) fn is_rotation<'a, T, A, B, I>(a: &[A], b: I) -> bool
+where
+    T: PartialEq + 'a,
+    A: Deref<Target = T>,
+    B: Deref<Target = T> + 'a,
+    I: Iterator<Item = &'a B>,
+{
+    let b: Vec<&T> = b.map(|x| &**x).collect();
+    if a.len() != b.len() {
+        return false;
+    }
+    if b.is_empty() {
+        return true;
+    }
+
+    let mut fail = vec![0; b.len()];
+    let mut k = 0;
+    for i in 1..b.len() {
+        while k > 0 && b[i] != b[k] {
+            k = fail[k - 1];
+        }
+        if b[i] == b[k] {
+            k += 1;
+        }
+        fail[i] = k;
+    }
+
+    let mut k = 0;
+    for i in 0..2 * a.len() - 1 {
+        let x = &*a[i % a.len()];
+        while k > 0 && x != b[k] {
+            k = fail[k - 1];
+        }
+        if x == b[k] {
+            k += 1;
+        }
+        if k == b.len() {
+            return true;
+        }
+    }
+    false
+}
+
+/// The first and last node of a chain linked through `next`, ending in `None`.
+type Run<T> = (Rcrfn<T>, Rcrfn<T>);
+
+/// Holds every node of a list being sorted, as runs,
+/// and links them back into the list when dropped, even if the comparator panics.
+struct SortGuard<'a, T> {
+    list: &'a mut CircularLinkedList<T>,
+    len: usize,
+    /// The nodes not binned yet.
+    unsorted: Option<Run<T>>,
+    /// `bins[i]` holds a sorted run of `2^i` nodes, which all come before the unsorted ones.
+    bins: Vec<Option<Run<T>>>,
+    /// The merge in progress, see [`merge`](Self::merge).
+    merged: Option<Run<T>>,
+    earlier: Option<Run<T>>,
+    later: Option<Run<T>>,
+}
+
+impl<T> SortGuard<'_, T> {
+    /// Merges two sorted runs, taking from `earlier` first when elements compare equal.
+    fn merge<F>(&mut self, earlier: Run<T>, later: Run<T>, compare: &mut F) -> Run<T>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.earlier = Some(earlier);
+        self.later = Some(later);
+
+        loop {
+            let (a, b) = (
+                &self.earlier.as_ref().unwrap().0,
+                &self.later.as_ref().unwrap().0,
+            );
+            let from_b = compare(&b.borrow().value, &a.borrow().value) == Ordering::Less;
+            let source = if from_b {
+                &mut self.later
+            } else {
+                &mut self.earlier
+            };
+            let (taken, source_tail) = source.take().unwrap();
+            if let Some(next) = taken.borrow_mut().next.take() {
+                *source = Some((next, source_tail));
+            }
+            let used_up = source.is_none();
+
+            match &mut self.merged {
+                Some((_, tail)) => {
+                    *tail.borrow_mut().next = Some(taken.clone());
+                    *tail = taken;
+                }
+                None => self.merged = Some((taken.clone(), taken)),
+            }
+
+            // One run is used up, the other one follows as it is.
+            if used_up {
+                let (other, other_tail) = self.earlier.take().or(self.later.take()).unwrap();
+                let (head, tail) = self.merged.take().unwrap();
+                *tail.borrow_mut().next = Some(other);
+                return (head, other_tail);
+            }
+        }
+    }
+}
+
+impl<T> Drop for SortGuard<'_, T> {
+    fn drop(&mut self) {
+        let runs = [self.merged.take(), self.earlier.take(), self.later.take()]
+            .into_iter()
+            .chain(self.bins.drain(..).rev())
+            .chain([self.unsorted.take()])
+            .flatten();
+
+        let mut circle: Option<Run<T>> = None;
+        for (head, tail) in runs {
+            circle = Some(match circle {
+                Some((first, last)) => {
+                    *last.borrow_mut().next = Some(head);
+                    (first, tail)
+                }
+                None => (head, tail),
+            });
+        }
+
+        let (head, tail) = circle.unwrap();
+        *tail.borrow_mut().next = Some(head.clone());
+        self.list.head = Some(head);
+        self.list.tail = Some(tail);
+        self.list.len = self.len;
+        self.list.relink_prevs();
+        self.list.reshaped();
+    }
+}
+
+/// Index of the lexicographically smallest rotation of `s`, using Booth's algorithm.
+pub(crate) fn least_rotation<T: Ord, R: Deref<Target = T>>(s: &[R]) -> usize {
+    let n = s.len();
+    let at = |i: usize| &*s[i % n];
+
+    let mut fail: Vec<isize> = vec![-1; 2 * n];
+    let mut k = 0;
+    for j in 1..2 * n {
+        let sj = at(j);
+        let mut i = fail[j - k - 1];
+        while i != -1 && sj != at(k + i as usize + 1) {
+            if sj < at(k + i as usize + 1) {
+                k = j - i as usize - 1;
+            }
+            i = fail[i as usize];
+        }
+        if i == -1 && sj != at(k) {
+            if sj < at(k) {
+                k = j;
+            }
+            fail[j - k] = -1;
+        } else {
+            fail[j - k] = i + 1;
+        }
+    }
+    k
+}
+
+fn gcd(mut a: usize, mut b: usize) -> usize {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Unlinks the nodes one at a time instead of letting the `Rc` chain drop recursively,
+/// so even very long lists can't overflow the stack.
+///
+/// Every node is unlinked, even those still referenced elsewhere,
+/// so an iterator outliving the list yields the node it holds and then stops.
+/// A node that is borrowed while the list drops keeps its link to the next one,
+/// which is released along with it,
+/// so only a list of a single borrowed node is leaked.
+///
+/// ```
+/// # use garlic::circular_linked_list::*;
+/// let cll: CircularLinkedList<_> = (0..3_000_000).collect();
+/// drop(cll);
+///
+/// let cll: CircularLinkedList<_> = (0..3_000_000).collect();
+/// let mut iter = cll.iter();
+/// iter.next();
+/// drop(cll);
+/// assert_eq!(iter.map_copied().collect::<Vec<_>>(), [1]);
+///
+/// // Borrowed nodes don't stop the others from being unlinked.
+/// let cll: CircularLinkedList<_> = (0..3_000_000).collect();
+/// let second = cll.iter().nth(1).unwrap();
+/// let borrowed = second.borrow_mut();
+/// drop(cll);
+/// drop(borrowed);
+/// drop(second);
+/// ```
+///
+/// ```
+/// # use garlic::circular_linked_list::*;
+/// use std::rc::Rc;
+///
+/// let dropped = Rc::new(());
+/// let cll: CircularLinkedList<_> = (0..5).map(|_| dropped.clone()).collect();
+/// let head = cll.iter().next().unwrap();
+/// let borrowed = head.borrow();
+/// drop(cll);
+/// // The borrowed head still links to the second node, the rest are gone.
+/// assert_eq!(Rc::strong_count(&dropped), 3);
+/// drop(borrowed);
+/// drop(head);
+/// assert_eq!(Rc::strong_count(&dropped), 1);
+/// ```
+impl<T> Drop for CircularLinkedList<T> {
+    fn drop(&mut self) {
+        let tail = self.tail.take();
+        let mut cursor = self.head.take();
+        for _ in 0..self.len {
+            let Some(rcrfn) = cursor else {
+                break;
+            };
+            cursor = match rcrfn.try_borrow_mut() {
+                Ok(mut node) => node.next.take(),
+                // A node borrowed elsewhere keeps its link, but the ones after it can still be unlinked.
+                Err(_) => match rcrfn.try_borrow() {
+                    Ok(node) => node.next.clone(),
+                    Err(_) => {
+                        Self::unlink_back(tail, &rcrfn);
+                        break;
+                    }
+                },
+            };
+        }
+    }
+}
+
+impl<T> CircularLinkedList<T> {
+    /// Unlinks the nodes from `tail` backward until `stop`, for when `stop` is mutably borrowed
+    /// and the ones after it can't be reached going forward.
+    fn unlink_back(tail: Pointer<T>, stop: &Rcrfn<T>) {
+        let mut cursor = tail;
+        while let Some(rcrfn) = cursor {
+            if Rc::ptr_eq(&rcrfn, stop) {
+                break;
+            }
+            cursor = match rcrfn.try_borrow_mut() {
+                Ok(mut node) => {
+                    node.next.take();
+                    node.prev.upgrade()
+                }
+                Err(_) => None,
+            };
+        }
+    }
+}
+
+/// Something that picks out an element of a [`CircularLinkedList`]:
+/// a position, wrapping around the circle, or a [`NodeHandle`].
+///
+/// Used by [`CircularLinkedList::get`] and friends. This trait is sealed.
+pub trait CllIndex<T>: sealed::Sealed {
+    #[doc(hidden)]
+    fn try_element(self, list: &CircularLinkedList<T>) -> Result<&Element<T>, CllError>;
+}
+
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for usize {}
+    impl<T> Sealed for &super::NodeHandle<T> {}
+}
+
+impl<T> CllIndex<T> for usize {
+    fn try_element(self, list: &CircularLinkedList<T>) -> Result<&Element<T>, CllError> {
+        if list.is_empty() {
+            return Err(CllError::Empty);
+        }
+        Ok(&list.try_nodes()?[self % list.len])
+    }
+}
+
+impl<T> CllIndex<T> for &NodeHandle<T> {
+    fn try_element(self, list: &CircularLinkedList<T>) -> Result<&Element<T>, CllError> {
+        let node = list.try_resolve(self)?;
+        let nodes = list.try_nodes()?;
+        Ok(nodes.iter().find(|e| Rc::ptr_eq(&e.0, &node)).unwrap())
+    }
+}
+
+/// Indexes by position, wrapping around the circle, or by [`NodeHandle`],
+/// like [`get`](CircularLinkedList::get).
+///
+/// There's no `IndexMut`, an [`Element`] is borrowed mutably through `&` instead.
+///
+/// ```
+/// # use garlic::circular_linked_list::*;
+/// let mut cll = CircularLinkedList::from([1, 2, 3]);
+/// assert_eq!(*cll[3].borrow(), 1);
+/// *cll[4].borrow_mut() += 10;
+/// let handle = cll.handle_at(1).unwrap();
+/// assert_eq!(*cll[&handle].borrow(), 12);
+/// ```
+///
+/// ```compile_fail
+/// # use garlic::circular_linked_list::*;
+/// let mut a = CircularLinkedList::from([1, 2]);
+/// let b = CircularLinkedList::from([3, 4]);
+/// a[0] = b[0].clone();
+/// ```
+///
+/// # Panics
+/// Panics if the list is empty, if the handle is no longer part of this list,
+/// or if the list has been modified since it was last walked and an element is mutably borrowed.
+impl<T, I: CllIndex<T>> Index<I> for CircularLinkedList<T> {
+    type Output = Element<T>;
+
+    fn index(&self, index: I) -> &Element<T> {
+        index.try_element(self).unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+/// An element of a [`CircularLinkedList`], as returned by indexing.
+///
+/// The value has to be borrowed, like through [`get`](CircularLinkedList::get),
+/// since a [`CllIter`] may be borrowing the same node.
+pub struct Element<T>(Rcrfn<T>);
+
+impl<T> Element<T> {
+    /// Borrows the value.
+    ///
+    /// # Panics
+    /// Panics if the value is mutably borrowed.
+    pub fn borrow(&self) -> Ref<'_, T> {
+        Ref::map(self.0.borrow(), |n| &n.value)
+    }
+
+    /// Mutably borrows the value.
+    ///
+    /// # Panics
+    /// Panics if the value is borrowed.
+    pub fn borrow_mut(&self) -> RefMut<'_, T> {
+        RefMut::map(self.0.borrow_mut(), |n| &mut n.value)
+    }
+
+    /// Like [`borrow`](Self::borrow), but fails instead of panicking.
+    pub fn try_borrow(&self) -> Result<Ref<'_, T>, CllError> {
+        Ok(Ref::map(self.0.try_borrow()?, |n| &n.value))
+    }
+
+    /// Like [`borrow_mut`](Self::borrow_mut), but fails instead of panicking.
+    pub fn try_borrow_mut(&self) -> Result<RefMut<'_, T>, CllError> {
+        Ok(RefMut::map(self.0.try_borrow_mut()?, |n| &mut n.value))
+    }
+}
+
+impl<T> Clone for Element<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for Element<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.try_borrow() {
+            Ok(value) => f.debug_tuple("Element").field(&*value).finish(),
+            Err(_) => f.write_str("Element(<borrowed>)"),
+        }
+    }
+}
+
+/// Iterator over the nodes of a [`CircularLinkedList`].
+///
+/// It doesn't borrow the list, so the list can still be modified while iterating.
+/// How the iterator reacts to that depends on its mode:
+///
+/// - By default it keeps the `tail` it saw when created,
+///   so after [`once`](Self::once) it stops there even if that node is no longer the tail.
+///   Once that node can't be reached from its next node within the list,
+///   e.g. because it was removed or split off, it stops right away.
+/// - After [`fail_fast`](Self::fail_fast), it stops as soon as the list has been modified.
+/// - After [`live`](Self::live), it follows the list as it is now,
+///   stopping at the current tail, or right away if its next node has been removed.
+///
+/// Linking, unlinking and rotating nodes count as modifications:
+/// `push`, `push_front`, `insert`, `insert_after_node`, `pop_front`, `pop_back`, `remove`,
+/// `remove_node`, `truncate`, `clear`, `append` (of both lists), `split_off`,
+/// `split_at_node`, `splice`, `retain`, `retain_mut`, `extract_if`, the `dedup` methods,
+/// the `rotate_` methods, `canonicalize`, `reverse` and the `sort` methods,
+/// along with the editing methods of [`CursorMut`].
+/// Changing values in place, e.g. through [`iter_values_mut`](CircularLinkedList::iter_values_mut),
+/// doesn't.
+///
+/// ```
+/// # use garlic::circular_linked_list::*;
+/// let mut cll = CircularLinkedList::from([1, 2, 3]);
+///
+/// let mut snapshot = cll.iter_once();
+/// let mut fail_fast = cll.iter_once().fail_fast();
+/// let mut live = cll.iter_once().live();
+/// for iter in [&mut snapshot, &mut fail_fast, &mut live] {
+///     iter.next();
+/// }
+///
+/// cll.push(4);
+/// assert_eq!(snapshot.map_copied().collect::<Vec<_>>(), [2, 3]);
+/// assert!(fail_fast.list_modified());
+/// assert_eq!(fail_fast.map_copied().collect::<Vec<_>>(), []);
+/// assert_eq!(live.map_copied().collect::<Vec<_>>(), [2, 3, 4]);
+///
+/// // The saved tail moved to another list, so the snapshot can't reach it anymore.
+/// let mut cll = CircularLinkedList::from([1, 2, 3, 4]);
+/// let mut snapshot = cll.iter_once();
+/// snapshot.next();
+/// let rest = cll.split_off(2);
+/// assert_eq!(snapshot.len(), 0);
+/// assert!(snapshot.next().is_none());
+/// assert_eq!(rest, CircularLinkedList::from([3, 4]));
+/// ```
+pub struct CllIter<T> {
+    cursor: Pointer<T>,
+    tail: Pointer<T>,
+    stop: bool,
+    len: usize,
+    pos: usize,
+    mode: Mode,
+    shape: Rc<Shape<T>>,
+    /// The list's generation when this iterator last looked at its shape.
+    generation: u64,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Snapshot,
+    FailFast,
+    Live,
+}
+
+impl<T> Clone for CllIter<T> {
+    fn clone(&self) -> Self {
+        Self {
+            cursor: self.cursor.clone(),
+            tail: self.tail.clone(),
+            stop: self.stop,
+            len: self.len,
+            pos: self.pos,
+            mode: self.mode,
+            shape: self.shape.clone(),
+            generation: self.generation,
+        }
+    }
+}
+
+impl<T> CllIter<T> {
+    pub fn once(mut self) -> Self {
+        self.stop = true;
+        self
+    }
+
+    /// Makes the iterator stop as soon as the list is modified.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mutations: [fn(&mut CircularLinkedList<i32>); 31] = [
+    ///     |cll| cll.push(9),
+    ///     |cll| cll.push_front(9),
+    ///     |cll| cll.insert(2, 9),
+    ///     |cll| drop(cll.pop_front()),
+    ///     |cll| drop(cll.insert_after_node(&cll.handle_at(0).unwrap(), 9)),
+    ///     |cll| drop(cll.remove(2)),
+    ///     |cll| cll.truncate(3),
+    ///     |cll| cll.clear(),
+    ///     |cll| cll.rotate_forward(1),
+    ///     |cll| cll.rotate_backward(1),
+    ///     |cll| drop(cll.rotate_to(|&x| x == 3)),
+    ///     |cll| cll.append(&mut CircularLinkedList::from([9])),
+    ///     |cll| drop(cll.split_off(2)),
+    ///     |cll| drop(cll.split_at_node(&cll.handle_at(2).unwrap())),
+    ///     |cll| drop(cll.splice(2..3, [9])),
+    ///     |cll| cll.retain(|&x| x != 3),
+    ///     |cll| cll.retain_mut(|x| *x != 3),
+    ///     |cll| drop(cll.extract_if(|x| *x == 3).count()),
+    ///     |cll| cll.dedup_by(|a, _| *a == 3),
+    ///     |cll| cll.dedup_by_cyclic(|a, _| *a == 3),
+    ///     |cll| drop(cll.remove_node(&cll.handle_at(2).unwrap())),
+    ///     |cll| cll.reverse(),
+    ///     |cll| cll.sort(),
+    ///     |cll| cll.sort_by(|a, b| b.cmp(a)),
+    ///     |cll| cll.sort_by_key(|&x| x % 2),
+    ///     |cll| cll.sort_unstable_by(|a, b| a.cmp(b)),
+    ///     |cll| cll.cursor_mut().insert_after(9),
+    ///     |cll| cll.cursor_mut().insert_before(9),
+    ///     |cll| {
+    ///         let mut cursor = cll.cursor_mut();
+    ///         cursor.move_next();
+    ///         cursor.move_next();
+    ///         drop(cursor.remove_current());
+    ///     },
+    ///     |cll| {
+    ///         let mut cursor = cll.cursor_mut();
+    ///         cursor.move_next();
+    ///         drop(cursor.split_after());
+    ///     },
+    ///     |cll| drop(cll.try_push(9)),
+    /// ];
+    ///
+    /// for mutate in mutations {
+    ///     let mut cll = CircularLinkedList::from([1, 2, 3, 4]);
+    ///     let mut iter = cll.iter_once().fail_fast();
+    ///     let mut snapshot = cll.iter_once();
+    ///     iter.next();
+    ///     snapshot.next();
+    ///     assert!(!iter.list_modified());
+    ///
+    ///     mutate(&mut cll);
+    ///     assert!(iter.list_modified());
+    ///     assert_eq!(iter.len(), 0);
+    ///     assert!(iter.next().is_none());
+    ///     // Snapshots still end, at their saved tail or once it's out of reach.
+    ///     let left = snapshot.len();
+    ///     assert_eq!(snapshot.take(10).count(), left);
+    /// }
+    ///
+    /// // Appending moves the other list's nodes, so it's modified too.
+    /// let mut cll = CircularLinkedList::from([1, 2]);
+    /// let mut other = CircularLinkedList::from([3, 4]);
+    /// let iter = other.iter_once().fail_fast();
+    /// cll.append(&mut other);
+    /// assert!(iter.list_modified());
+    ///
+    /// // Editing values in place isn't a modification.
+    /// let mut cll = CircularLinkedList::from([1, 2, 3]);
+    /// let iter = cll.iter_once().fail_fast();
+    /// cll.iter_values_mut().for_each(|mut x| *x *= 10);
+    /// assert_eq!(iter.map_copied().collect::<Vec<_>>(), [10, 20, 30]);
+    /// ```
+    pub fn fail_fast(mut self) -> Self {
+        self.mode = Mode::FailFast;
+        self
+    }
+
+    /// Makes the iterator follow the list as it is modified.
+    ///
+    /// After [`once`](Self::once), it stops at whatever node is the tail when it gets there.
+    /// If the node it would yield next is removed, or moved to another list, it stops.
+    ///
+    /// ## Expensive
+    /// Catching up after a modification walks from the iterator's position to the tail.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut cll = CircularLinkedList::from([1, 2, 3, 4]);
+    /// let mut iter = cll.iter_once().live();
+    /// assert_eq!(iter.next().map(|x| x.borrow().value), Some(1));
+    ///
+    /// cll.push(5);
+    /// cll.insert(2, 9);
+    /// assert_eq!(iter.len(), 5);
+    /// cll.rotate_forward(3);
+    /// assert_eq!(iter.map_copied().collect::<Vec<_>>(), [2, 9]);
+    ///
+    /// let mut iter = cll.iter_once().live();
+    /// iter.next();
+    /// cll.truncate(3);
+    /// assert_eq!(iter.map_copied().collect::<Vec<_>>(), [4, 5]);
+    ///
+    /// let mut iter = cll.iter_once().live();
+    /// iter.next();
+    /// cll.truncate(1);
+    /// assert!(iter.next().is_none());
+    /// ```
+    pub fn live(mut self) -> Self {
+        self.mode = Mode::Live;
+        self
+    }
+
+    /// Whether the list has been modified since the iterator was created,
+    /// or, in [`live`](Self::live) mode, since it last caught up.
+    pub fn list_modified(&self) -> bool {
+        self.generation != self.shape.generation.get()
+    }
+
+    /// Catches up with the list's current shape, counting the nodes left to the tail,
+    /// which in [`live`](Self::live) mode is the list's current one.
+    ///
+    /// Stops the iterator if the tail can't be reached from its next node within the list.
+    fn catch_up(&mut self) {
+        self.generation = self.shape.generation.get();
+        if self.mode == Mode::Live {
+            self.tail = self.shape.tail.borrow().upgrade();
+        }
+
+        let (Some(cursor), Some(tail)) = (&self.cursor, &self.tail) else {
+            self.cursor = None;
+            return;
+        };
+
+        let len = self.shape.len.get();
+        let mut node = cursor.clone();
+        let mut remaining = 1;
+        loop {
+            // Unlinked nodes have no `next`.
+            let next = node.borrow().next.clone();
+            let Some(next) = next.filter(|_| remaining <= len) else {
+                self.cursor = None;
+                return;
+            };
+            if Rc::ptr_eq(&node, tail) {
+                break;
+            }
+            node = next;
+            remaining += 1;
+        }
+
+        self.len = len;
+        self.pos = len - remaining;
+    }
+
+    /// Copies the inner value of each `Rc<RefCell<Node<T>>>`
+    ///
+    /// Equivelent to `cll.map(|x| x.borrow().value)`
+    pub fn map_copied(self) -> impl Iterator<Item = T>
+    where
+        T: Copy,
+    {
+        self.map(|x| x.borrow().value)
+    }
+
+    /// Clones the inner value of each `Rc<RefCell<Node<T>>>`
+    ///
+    /// Equivelent to `cll.map(|x| x.borrow().value.clone())`
+    pub fn map_cloned(self) -> impl Iterator<Item = T>
+    where
+        T: Clone,
+    {
+        self.map(|x| x.borrow().value.clone())
+    }
+}
+
+impl<T> Iterator for CllIter<T> {
+    type Item = Rcrfn<T>;
+
+    fn next(&mut self) -> Pointer<T> {
+        if self.list_modified() {
+            match self.mode {
+                Mode::Snapshot if !self.stop => {}
+                Mode::FailFast => self.cursor = None,
+                Mode::Snapshot | Mode::Live => self.catch_up(),
+            }
+        }
+
+        let r = self.cursor.take()?;
+
+        if !self.stop || !Rc::ptr_eq(&r, self.tail.as_ref().unwrap()) {
+            self.cursor = r.borrow().next.clone();
+            self.pos = (self.pos + 1) % self.len;
+        }
+
+        Some(r)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.list_modified() {
+            match self.mode {
+                Mode::Snapshot if !self.stop => {}
+                Mode::FailFast => return (0, Some(0)),
+                Mode::Snapshot | Mode::Live => {
+                    let mut caught_up = self.clone();
+                    caught_up.catch_up();
+                    return caught_up.size_hint();
+                }
+            }
+        }
+
+        match (&self.cursor, self.stop) {
+            (None, _) => (0, Some(0)),
+            (Some(_), false) => (usize::MAX, None),
+            (Some(_), true) => (self.len - self.pos, Some(self.len - self.pos)),
+        }
+    }
+}
+
+/// Only meaningful after [`CllIter::once`].
+/// Calling [`len`](ExactSizeIterator::len) on a never ending iterator panics.
+///
+/// ```
+/// # use garlic::circular_linked_list::*;
+/// let cll: CircularLinkedList<_> = (1..=5).collect();
+/// let mut iter = cll.iter_once();
+/// assert_eq!(iter.len(), 5);
+/// iter.next();
+/// assert_eq!(iter.len(), 4);
+/// ```
+impl<T> ExactSizeIterator for CllIter<T> {}
+
+/// A handle to an element of a [`CircularLinkedList`].
+///
+/// Holds a [`Weak`] reference to the node, so it doesn't keep the element alive.
+/// List methods taking a handle check that it still refers to an element of that list.
+///
+/// Created by [`CircularLinkedList::push_handle`] and friends.
+pub struct NodeHandle<T> {
+    node: Weak<RefCell<Node<T>>>,
+}
+
+impl<T> NodeHandle<T> {
+    fn new(node: &Rcrfn<T>) -> Self {
+        Self {
+            node: Rc::downgrade(node),
+        }
+    }
+}
+
+impl<T> Clone for NodeHandle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            node: self.node.clone(),
+        }
+    }
+}
+
+/// A cursor over a [`CircularLinkedList`] that can insert, remove and replace elements.
+///
+/// Since the list is circular, the cursor never runs off the end:
+/// moving past the tail brings it back to the head.
+///
+/// Created by [`CircularLinkedList::cursor_mut`].
+pub struct CursorMut<'a, T> {
+    list: &'a mut CircularLinkedList<T>,
+    current: Pointer<T>,
+    prev: Pointer<T>,
+    /// Keeps the node [`peek_next`](Self::peek_next) borrows alive.
+    peeked: Pointer<T>,
+    index: usize,
+}
+
+impl<T> CursorMut<'_, T> {
+    /// Index of the current element, counting from the head,
+    /// or `None` if the list is empty.
+    pub fn index(&self) -> Option<usize> {
+        self.current.as_ref().map(|_| self.index)
+    }
+
+    /// Moves to the next element, wrapping from the tail back to the head.
+    pub fn move_next(&mut self) {
+        let Some(current) = self.current.take() else {
+            return;
+        };
+        self.current = current.borrow().next.clone();
+        self.prev = Some(current);
+        self.index = (self.index + 1) % self.list.len;
+    }
+
+    /// Borrows the current element, or returns `None` if the list is empty.
+    pub fn current(&mut self) -> Option<RefMut<'_, T>> {
+        let node = self.current.as_deref()?;
+        Some(RefMut::map(node.borrow_mut(), |n| &mut n.value))
+    }
+
+    /// Borrows the element after the current one, or returns `None` if the list is empty.
+    ///
+    /// In a single element list, this is the current element itself.
+    pub fn peek_next(&mut self) -> Option<RefMut<'_, T>> {
+        self.peeked = self.current.as_ref()?.borrow().next.clone();
+        let node = self.peeked.as_ref().unwrap();
+        Some(RefMut::map(node.borrow_mut(), |n| &mut n.value))
+    }
+
+    /// Inserts an element after the current one.
+    ///
+    /// If the list is empty, the new element becomes the current one.
+    pub fn insert_after(&mut self, value: T) {
+        let Some(current) = &self.current else {
+            return self.insert_into_empty(value);
+        };
+
+        let node = CircularLinkedList::link_after(current, value);
+        self.list.len += 1;
+        if Rc::ptr_eq(current, self.list.tail.as_ref().unwrap()) {
+            self.list.tail = Some(node);
+        }
+        self.list.reshaped();
+    }
+
+    /// Inserts an element before the current one.
+    ///
+    /// If the current element is the head, the new element becomes the head.
+    /// If the list is empty, the new element becomes the current one.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut cll = CircularLinkedList::from([2, 4]);
+    /// let mut cursor = cll.cursor_mut();
+    /// cursor.insert_before(1);
+    /// cursor.move_next();
+    /// cursor.insert_before(3);
+    /// cursor.insert_after(5);
+    /// assert_eq!(cursor.index(), Some(3));
+    /// assert_eq!(cll, CircularLinkedList::from([1, 2, 3, 4, 5]));
+    /// ```
+    pub fn insert_before(&mut self, value: T) {
+        let (Some(current), Some(prev)) = (&self.current, &self.prev) else {
+            return self.insert_into_empty(value);
+        };
+
+        let node = CircularLinkedList::link_after(prev, value);
+        self.list.len += 1;
+        if Rc::ptr_eq(current, self.list.head.as_ref().unwrap()) {
+            self.list.head = Some(node.clone());
+        }
+        self.list.reshaped();
+        self.prev = Some(node);
+        self.index += 1;
+    }
+
+    fn insert_into_empty(&mut self, value: T) {
+        self.list.push_first(value);
+        self.current = self.list.head.clone();
+        self.prev = self.list.tail.clone();
+        self.index = 0;
+    }
+
+    /// Removes the current element and returns it, moving the cursor to the next element.
+    ///
+    /// Returns `None` if the list is empty.
+    ///
+    /// # Panics
+    /// Panics if the node is still referenced elsewhere, e.g. by a live [`CllIter`],
+    /// or if it or its neighbours are borrowed.
+    /// See [`try_remove_current`](Self::try_remove_current) for a non-panicking version.
+    pub fn remove_current(&mut self) -> Option<T> {
+        found(self.try_remove_current())
+    }
+
+    /// Like [`remove_current`](Self::remove_current),
+    /// but reports every failure as a [`CllError`].
+    ///
+    /// The list and the cursor are left untouched on failure.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut cll = CircularLinkedList::from([1, 2, 3]);
+    /// let mut iter = cll.iter();
+    /// iter.next();
+    ///
+    /// let mut cursor = cll.cursor_mut();
+    /// cursor.move_next();
+    /// assert_eq!(cursor.try_remove_current(), Err(CllError::NodeInUse));
+    /// assert_eq!(cursor.current().as_deref(), Some(&2));
+    /// drop(iter);
+    /// assert_eq!(cursor.try_remove_current(), Ok(2));
+    /// assert_eq!(cursor.current().as_deref(), Some(&3));
+    /// assert_eq!(cll, CircularLinkedList::from([1, 3]));
+    /// ```
+    pub fn try_remove_current(&mut self) -> Result<T, CllError> {
+        let current = Rc::downgrade(self.current.as_ref().ok_or(CllError::Empty)?);
+        // Only `prev` may be held while checking, `current` is found again on failure.
+        self.current = None;
+        self.peeked = None;
+        let prev = self.prev.take().unwrap();
+        if let Err(e) = self.list.check_unlink_after(&prev) {
+            self.current = current.upgrade();
+            self.prev = Some(prev);
+            return Err(e);
+        }
+        let node = self.list.unlink_after(&prev);
+
+        if self.list.is_empty() {
+            drop(prev);
+            self.index = 0;
+        } else {
+            self.current = prev.borrow().next.clone();
+            self.prev = Some(prev);
+            self.index %= self.list.len;
+        }
+        Ok(CircularLinkedList::into_value(node))
+    }
+
+    /// Replaces the current element, returning the old one.
+    ///
+    /// If the list is empty, `value` is inserted as the only element and `None` is returned.
+    pub fn replace_current(&mut self, value: T) -> Option<T> {
+        let Some(current) = &self.current else {
+            self.insert_into_empty(value);
+            return None;
+        };
+        Some(std::mem::replace(&mut current.borrow_mut().value, value))
+    }
+
+    /// Splits the list after the current element.
+    ///
+    /// The cursor's list keeps the elements from the head up to and including the current one,
+    /// everything after it, up to the tail, is returned as a new list.
+    ///
+    /// ```
+    /// # use garlic::circular_linked_list::*;
+    /// let mut cll = CircularLinkedList::from([1, 2, 3, 4, 5]);
+    /// let mut cursor = cll.cursor_mut();
+    /// cursor.move_next();
+    /// let rest = cursor.split_after();
+    /// assert_eq!(cll, CircularLinkedList::from([1, 2]));
+    /// assert_eq!(rest, CircularLinkedList::from([3, 4, 5]));
+    /// ```
+    pub fn split_after(&mut self) -> CircularLinkedList<T> {
+        let mut rest = CircularLinkedList::new();
+        let Some(current) = &self.current else {
+            return rest;
+        };
+        let tail = self.list.tail.clone().unwrap();
+        if Rc::ptr_eq(current, &tail) {
+            return rest;
+        }
+
+        let head = self.list.head.clone();
+        let first = std::mem::replace(&mut *current.borrow_mut().next, head.clone());
+        *tail.borrow_mut().next = first.clone();
+        head.unwrap().borrow_mut().prev = Rc::downgrade(current);
+        first.as_ref().unwrap().borrow_mut().prev = Rc::downgrade(&tail);
+
+        rest.head = first;
+        rest.tail = Some(tail);
+        rest.len = self.list.len - self.index - 1;
+        for node in rest.iter_once() {
+            node.borrow_mut().owner = Some(rest.owner.clone());
+        }
+        self.list.tail = Some(current.clone());
+        self.list.len = self.index + 1;
+        self.list.reshaped();
+        rest.reshaped();
+        rest
+    }
+}
+
+/// Iterator over borrowed values of a [`CircularLinkedList`].
+///
+/// Created by [`CircularLinkedList::iter_values`] and [`CircularLinkedList::iter_values_once`].
+///
+/// The values are borrowed from nodes the list has collected up front,
+/// so they stay valid even if the nodes are relinked through a [`CllIter`] meanwhile.
+///
+/// ```
+/// # use garlic::circular_linked_list::*;
+/// let a = CircularLinkedList::from(['a', 'b', 'c']);
+/// let b = CircularLinkedList::from(['d', 'e']);
+/// let mut values = a.iter_values_once();
+/// assert_eq!(values.next().as_deref(), Some(&'a'));
+///
+/// // Swapping whole nodes swaps their links too.
+/// let (node_a, node_d) = (a.iter().next().unwrap(), b.iter().next().unwrap());
+/// std::mem::swap(&mut *node_a.borrow_mut(), &mut *node_d.borrow_mut());
+/// drop(b);
+/// assert_eq!(values.next().as_deref(), Some(&'b'));
+/// ```
+#[derive(Clone)]
+pub struct Values<'a, T> {
+    nodes: &'a [Element<T>],
+    pos: usize,
+    remaining: Option<usize>,
+}
+
+impl<'a, T> Iterator for Values<'a, T> {
+    type Item = Ref<'a, T>;
+
+    fn next(&mut self) -> Option<Ref<'a, T>> {
+        let nodes = self.nodes;
+        if nodes.is_empty() {
+            return None;
+        }
+
+        match &mut self.remaining {
+            Some(0) => return None,
+            Some(remaining) => *remaining -= 1,
+            None => {}
+        }
+
+        let element = &nodes[self.pos];
+        self.pos = (self.pos + 1) % nodes.len();
+        Some(element.borrow())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match (self.nodes.is_empty(), self.remaining) {
+            (true, _) => (0, Some(0)),
+            (false, None) => (usize::MAX, None),
+            (false, Some(remaining)) => (remaining, Some(remaining)),
+        }
+    }
+}
+
+/// Iterator over borrowed values of a [`CircularLinkedList`]
+/// that reports borrow conflicts instead of panicking.
+///
+/// Created by [`CircularLinkedList::try_iter_values`].
+pub struct TryValues<'a, T> {
+    nodes: std::slice::Iter<'a, Element<T>>,
+    /// Set when the list couldn't be walked, reported before anything else.
+    error: Option<CllError>,
+}
+
+impl<'a, T> Iterator for TryValues<'a, T> {
+    type Item = Result<Ref<'a, T>, CllError>;
+
+    fn next(&mut self) -> Option<Result<Ref<'a, T>, CllError>> {
+        if let Some(error) = self.error.take() {
+            return Some(Err(error));
+        }
+
+        let result = self.nodes.next()?.try_borrow();
+        if result.is_err() {
+            self.nodes = [].iter();
+        }
+        Some(result)
+    }
+}
+
+/// Iterator over mutably borrowed values of a [`CircularLinkedList`].
+///
+/// Created by [`CircularLinkedList::iter_values_mut`].
+pub struct ValuesMut<'a, T> {
+    nodes: std::slice::Iter<'a, Element<T>>,
+}
+
+impl<'a, T> Iterator for ValuesMut<'a, T> {
+    type Item = RefMut<'a, T>;
+
+    fn next(&mut self) -> Option<RefMut<'a, T>> {
+        Some(self.nodes.next()?.borrow_mut())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.nodes.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for ValuesMut<'_, T> {}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for CircularLinkedList<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        let mut l = f.debug_list();
+        for rcrfn in self.iter_once() {
+            l.entry(&rcrfn.borrow().value);
+        }
+        l.finish()
+    }
+}
+
+/// Deep clone: every node is reallocated, no `Rc` is shared with the original.
+///
+/// ```
+/// # use garlic::circular_linked_list::*;
+/// let cll = CircularLinkedList::from([1, 2, 3]);
+/// let mut clone = cll.clone();
+/// clone.iter_values_mut().for_each(|mut x| *x *= 10);
+/// assert_eq!(format!("{cll:?} {clone:?}"), "[1, 2, 3] [10, 20, 30]");
+/// ```
+impl<T: Clone> Clone for CircularLinkedList<T> {
+    fn clone(&self) -> Self {
+        self.iter_values_once().map(|x| T::clone(&x)).collect()
+    }
+}
+
+/// Lists are equal if they have the same elements in the same order, starting from the head.
+///
+/// ```
+/// # use garlic::circular_linked_list::*;
+/// let mut cll = CircularLinkedList::from([1, 2, 3]);
+/// assert_eq!(cll, CircularLinkedList::from([1, 2, 3]));
+/// cll.rotate_forward(1);
+/// assert_ne!(cll, CircularLinkedList::from([1, 2, 3]));
+/// ```
+impl<T: PartialEq> PartialEq for CircularLinkedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len
+            && self
+                .iter_values_once()
+                .zip(other.iter_values_once())
+                .all(|(a, b)| *a == *b)
+    }
+}
+
+impl<T: Eq> Eq for CircularLinkedList<T> {}
+
+impl<T: Hash> Hash for CircularLinkedList<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len.hash(state);
+        for x in self.iter_values_once() {
+            x.hash(state);
+        }
+    }
+}
+
+/// Lexicographic comparison, starting from the head.
+///
+/// ```
+/// # use garlic::circular_linked_list::*;
+/// assert!(CircularLinkedList::from([1, 2]) < CircularLinkedList::from([1, 3]));
+/// assert!(CircularLinkedList::from([1, 2]) < CircularLinkedList::from([1, 2, 0]));
+/// ```
+impl<T: PartialOrd> PartialOrd for CircularLinkedList<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        for (a, b) in self.iter_values_once().zip(other.iter_values_once()) {
+            match (*a).partial_cmp(&*b) {
+                Some(Ordering::Equal) => {}
+                non_eq => return non_eq,
+            }
+        }
+        self.len.partial_cmp(&other.len)
+    }
+}
+
+impl<T: Ord> Ord for CircularLinkedList<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        for (a, b) in self.iter_values_once().zip(other.iter_values_once()) {
+            match (*a).cmp(&*b) {
+                Ordering::Equal => {}
+                non_eq => return non_eq,
+            }
+        }
+        self.len.cmp(&other.len)
+    }
+}
+
+impl<T> std::iter::FromIterator<T> for CircularLinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut cll = Self::new();
+        cll.extend(iter);
+        cll
+    }
+}
+
+/// Pushes every element after the tail.
+///
+/// ```
+/// # use garlic::circular_linked_list::*;
+/// let mut cll: CircularLinkedList<_> = (1..=2).collect();
+/// cll.extend(3..=4);
+/// cll.extend(&[5, 6]);
+/// assert_eq!(format!("{cll:?}"), "[1, 2, 3, 4, 5, 6]");
+/// ```
+impl<T> Extend<T> for CircularLinkedList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.push(x);
+        }
+    }
+}
+
+impl<'a, T: Copy + 'a> Extend<&'a T> for CircularLinkedList<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<T> From<Vec<T>> for CircularLinkedList<T> {
+    fn from(vec: Vec<T>) -> Self {
+        vec.into_iter().collect()
+    }
+}
+
+/// ```
+/// # use garlic::circular_linked_list::*;
+/// let cll = CircularLinkedList::from([1, 2, 3]);
+/// assert_eq!(format!("{cll:?}"), "[1, 2, 3]");
+/// ```
+impl<T, const N: usize> From<[T; N]> for CircularLinkedList<T> {
+    fn from(array: [T; N]) -> Self {
+        array.into_iter().collect()
+    }
+}
+
+impl<T> From<VecDeque<T>> for CircularLinkedList<T> {
+    fn from(deque: VecDeque<T>) -> Self {
+        deque.into_iter().collect()
+    }
+}
+
+/// Collects the elements starting at the head.
+///
+/// Elements still referenced elsewhere are skipped, see [`CircularLinkedList::into_iter`].
+///
+/// ```
+/// # use garlic::circular_linked_list::*;
+/// let mut cll = CircularLinkedList::from(vec![1, 2, 3]);
+/// cll.rotate_forward(1);
+/// assert_eq!(Vec::from(cll), [2, 3, 1]);
+/// ```
+///
+/// # Panics
+/// Panics if any element is borrowed.
+impl<T> From<CircularLinkedList<T>> for Vec<T> {
+    fn from(cll: CircularLinkedList<T>) -> Self {
+        cll.into_iter().collect()
+    }
+}
+
+/// Collects the elements starting at the head, skipping those still referenced elsewhere.
+///
+/// # Panics
+/// Panics if any element is borrowed.
+impl<T> From<CircularLinkedList<T>> for VecDeque<T> {
+    fn from(cll: CircularLinkedList<T>) -> Self {
+        cll.into_iter().collect()
+    }
+}
+
+/// Collects the elements starting at the head, skipping those still referenced elsewhere.
+///
+/// # Panics
+/// Panics if any element is borrowed.
+impl<T> From<CircularLinkedList<T>> for LinkedList<T> {
+    fn from(cll: CircularLinkedList<T>) -> Self {
+        cll.into_iter().collect()
+    }
+}
+
+/// Consumes the list, yielding each element once, starting at the head.
+///
+/// The value of a node still referenced elsewhere, e.g. by a live [`CllIter`],
+/// can't be moved out, so that element is skipped and left to the reference.
+///
+/// ```
+/// # use garlic::circular_linked_list::*;
+/// let cll: CircularLinkedList<_> = ["Mike", "Hank", "Gus"].map(String::from).into_iter().collect();
+/// let mut names = vec![String::from("Walter")];
+/// names.extend(cll);
+/// assert_eq!(names, ["Walter", "Mike", "Hank", "Gus"]);
+///
+/// let cll = CircularLinkedList::from([1, 2, 3]);
+/// let two = cll.iter().nth(1).unwrap();
+/// assert_eq!(Vec::from(cll), [1, 3]);
+/// assert_eq!(two.borrow().value, 2);
+/// ```
+///
+/// # Panics
+/// Panics if any element is borrowed.
+impl<T> IntoIterator for CircularLinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(mut self) -> IntoIter<T> {
+        if let Some(mut prev) = self.tail.clone() {
+            for _ in 0..self.len {
+                match self.check_unlink_after(&prev) {
+                    Err(CllError::NodeInUse) => drop(self.unlink_after(&prev)),
+                    _ => {
+                        let next = prev.borrow().next.clone().unwrap();
+                        prev = next;
+                    }
+                }
+                if self.is_empty() {
+                    break;
+                }
+            }
+        }
+        IntoIter { list: self }
+    }
+}
+
+/// Iterates through the list once, like [`CircularLinkedList::iter_once`].
+///
+/// ```
+/// # use garlic::circular_linked_list::*;
+/// let cll: CircularLinkedList<_> = (1..=3).collect();
+/// let mut sum = 0;
+/// for rcrfn in &cll {
+///     sum += rcrfn.borrow().value;
+/// }
+/// assert_eq!(sum, 6);
+/// ```
+impl<T> IntoIterator for &CircularLinkedList<T> {
+    type Item = Rcrfn<T>;
+    type IntoIter = CllIter<T>;
+
+    fn into_iter(self) -> CllIter<T> {
+        self.iter_once()
+    }
+}
+
+/// Iterator that hops a fixed number of nodes around a [`CircularLinkedList`] every step.
+///
+/// Created by [`CircularLinkedList::step`] and [`CircularLinkedList::step_once`].
+pub struct Step<T> {
+    cursor: Pointer<T>,
+    /// Nodes to move forward between two elements, less than the list's length.
+    hops: usize,
+    /// Elements left to yield, `None` if the iterator never ends.
+    remaining: Option<usize>,
+}
+
+impl<T> Iterator for Step<T> {
+    type Item = Rcrfn<T>;
+
+    fn next(&mut self) -> Pointer<T> {
+        let node = self.cursor.take()?;
+
+        if let Some(remaining) = &mut self.remaining {
+            *remaining -= 1;
+            if *remaining == 0 {
+                return Some(node);
+            }
+        }
+
+        let mut next = node.clone();
+        for _ in 0..self.hops {
+            let Some(after) = next.borrow().next.clone() else {
+                // The node was removed from the list under us.
+                return Some(node);
+            };
+            next = after;
+        }
+        self.cursor = Some(next);
+        Some(node)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match (&self.cursor, self.remaining) {
+            (None, _) => (0, Some(0)),
+            (Some(_), None) => (usize::MAX, None),
+            (Some(_), Some(remaining)) => (remaining, Some(remaining)),
+        }
+    }
+}
+
+/// Iterator over the nodes of a [`CircularLinkedList`], from the tail back to the head.
+///
+/// Like [`CllIter`], it doesn't borrow the list.
+/// It yields at most as many nodes as the list had when it was created,
+/// and stops early if it reaches a node that has been removed.
+///
+/// Created by [`CircularLinkedList::reversed_iter_once`].
+pub struct ReversedIter<T> {
+    cursor: Pointer<T>,
+    remaining: usize,
+}
+
+impl<T> Iterator for ReversedIter<T> {
+    type Item = Rcrfn<T>;
+
+    fn next(&mut self) -> Pointer<T> {
+        let node = self.cursor.take()?;
+        self.remaining -= 1;
+        if self.remaining > 0 {
+            self.cursor = node.borrow().prev.upgrade();
+        }
+        Some(node)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.cursor {
+            None => (0, Some(0)),
+            Some(_) => (0, Some(self.remaining)),
+        }
+    }
+}
+
+/// Only meaningful for [`CircularLinkedList::step_once`].
+/// Calling [`len`](ExactSizeIterator::len) on a never ending iterator panics.
+impl<T> ExactSizeIterator for Step<T> {}
+
+/// Iterator that removes and yields the elements matching a predicate.
+///
+/// Created by [`CircularLinkedList::extract_if`].
+pub struct ExtractIf<'a, T, F> {
+    list: &'a mut CircularLinkedList<T>,
+    /// The node before the next one to check.
+    prev: Pointer<T>,
+    remaining: usize,
+    predicate: F,
+}
+
+impl<T, F> Iterator for ExtractIf<'_, T, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        while self.remaining > 0 {
+            self.remaining -= 1;
+            let prev = self.prev.take().unwrap();
+            let node = prev.borrow().next.clone().unwrap();
+            let extract = (self.predicate)(&mut node.borrow_mut().value);
+            if !extract {
+                self.prev = Some(node);
+                continue;
+            }
+
+            // Only `prev` may be held while checking, `node` is found again if it has to stay.
+            let kept = Rc::downgrade(&node);
+            drop(node);
+            if self.list.check_unlink_after(&prev).is_err() {
+                self.prev = kept.upgrade();
+                continue;
+            }
+            let node = self.list.unlink_after(&prev);
+            // The last node is its own predecessor, and must be let go of.
+            if Rc::ptr_eq(&node, &prev) {
+                drop(prev);
+            } else {
+                self.prev = Some(prev);
+            }
+            return Some(CircularLinkedList::into_value(node));
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.remaining))
+    }
+}
+
+/// Owning iterator over the elements of a [`CircularLinkedList`].
+///
+/// Created by [`CircularLinkedList::into_iter`].
+pub struct IntoIter<T> {
+    list: CircularLinkedList<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    /// # Panics
+    /// Panics if the next element is borrowed.
+    fn next(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len(), Some(self.list.len()))
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
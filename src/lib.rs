@@ -1 +1,5 @@
+pub mod arena_circular_list;
+pub mod circular_doubly_linked_list;
 pub mod circular_linked_list;
+pub mod raw_circular_linked_list;
+pub mod sync_circular_linked_list;
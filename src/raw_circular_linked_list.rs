@@ -0,0 +1,1092 @@
+use crate::circular_linked_list::{is_rotation, least_rotation};
+use std::{
+    cmp::Ordering,
+    collections::{LinkedList, VecDeque},
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    ptr::NonNull,
+};
+
+/// A circular singly linked list built on raw pointers.
+///
+/// Same as [`CircularLinkedList`](crate::circular_linked_list::CircularLinkedList),
+/// but every node is a plain [`Box`] linked through [`NonNull`] pointers,
+/// so there are no reference counts or borrow flags to pay for.
+/// Iterators hand out plain references to the nodes and values instead of `Rc`s and guards.
+///
+/// ## Miri
+/// The module has a few small unit tests meant for checking the unsafe code under Miri:
+/// `cargo +nightly miri test raw_circular_linked_list`.
+/// The doc tests shrink their large lists when run under Miri, so they can be checked too.
+///
+/// [`Cycle`]: std::iter::Cycle
+///
+/// # Usage
+/// ```
+/// # use garlic::raw_circular_linked_list::*;
+/// // You can push elements to it;
+/// let mut cll = CircularLinkedList::new();
+/// cll.push("Mike");
+/// cll.push("Hank");
+/// cll.push("Gus");
+///
+/// // It also implements FromIterator!
+/// let cll: CircularLinkedList<_> = (1..=7).collect();
+///
+/// // Nodes are borrowed from the list, their values are public.
+/// let names: Vec<_> = cll.iter().map(|x| x.borrow().value).take(8).collect();
+/// assert_eq!(names, [1, 2, 3, 4, 5, 6, 7, 1]);
+/// ```
+///
+/// Use [`iter`] to create a never ending iterator that goes from node to node forever.
+///
+/// Behavior is similar to a the [`Cycle`] iterator, but likely less useful.
+/// ```
+/// # use garlic::raw_circular_linked_list::*;
+/// let cll: CircularLinkedList<_> = "hello".chars().collect();
+/// let doubled: String = cll.iter().map_copied().take(cll.len() * 2).collect();
+/// assert_eq!(doubled, "hellohello");
+/// ```
+///
+/// [`iter`]: CircularLinkedList::iter
+pub struct CircularLinkedList<T> {
+    /// The head is always `tail.next`.
+    tail: Option<NonNull<Node<T>>>,
+    len: usize,
+    _marker: PhantomData<Box<Node<T>>>,
+}
+
+pub struct Node<T> {
+    pub value: T,
+    next: NonNull<Node<T>>,
+}
+
+// SAFETY: the list owns its nodes like a `Box` does, so it is as thread safe as `T`.
+unsafe impl<T: Send> Send for CircularLinkedList<T> {}
+unsafe impl<T: Sync> Sync for CircularLinkedList<T> {}
+
+// SAFETY: a node is only ever reached through its list, and only exposes its value.
+unsafe impl<T: Send> Send for Node<T> {}
+unsafe impl<T: Sync> Sync for Node<T> {}
+
+impl<T> Node<T> {
+    /// Returns the node itself, so code written against the `Rc<RefCell<Node<T>>>`
+    /// items of the other lists, like `x.borrow().value`, works unchanged.
+    #[allow(clippy::should_implement_trait)]
+    pub fn borrow(&self) -> &Self {
+        self
+    }
+}
+
+impl<T> Default for CircularLinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> CircularLinkedList<T> {
+    pub fn new() -> Self {
+        Self {
+            tail: None,
+            len: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tail.is_none()
+    }
+
+    pub fn push(&mut self, value: T) {
+        self.tail = Some(self.link_after_tail(value));
+    }
+
+    /// Pushes an element in front of the head, making it the new head.
+    ///
+    /// ```
+    /// # use garlic::raw_circular_linked_list::*;
+    /// let mut cll = CircularLinkedList::new();
+    /// cll.push_front(2);
+    /// cll.push_front(1);
+    /// cll.push(3);
+    /// assert_eq!(format!("{cll:?}"), "[1, 2, 3]");
+    /// ```
+    pub fn push_front(&mut self, value: T) {
+        self.link_after_tail(value);
+    }
+
+    /// Inserts an element so that it ends up at position `index`.
+    ///
+    /// `index` wraps around the circle, so inserting at `len` is the same as
+    /// inserting at `0`: the new element becomes the head.
+    ///
+    /// ```
+    /// # use garlic::raw_circular_linked_list::*;
+    /// let mut cll: CircularLinkedList<_> = "ace".chars().collect();
+    /// cll.insert(1, 'b');
+    /// cll.insert(7, 'd');
+    /// assert_eq!(format!("{cll:?}"), "['a', 'b', 'c', 'd', 'e']");
+    /// ```
+    pub fn insert(&mut self, index: usize, value: T) {
+        if self.len == 0 {
+            return self.push_front(value);
+        }
+
+        match index % self.len {
+            0 => self.push_front(value),
+            i => {
+                let prev = self.node_at(i - 1).unwrap();
+                // SAFETY: `prev` is a node of this list.
+                unsafe { Self::link_after(prev, value) };
+                self.len += 1;
+            }
+        }
+    }
+
+    /// Removes the head element and returns it, or `None` if the list is empty.
+    ///
+    /// ```
+    /// # use garlic::raw_circular_linked_list::*;
+    /// let mut cll: CircularLinkedList<_> = (1..=3).collect();
+    /// assert_eq!(cll.pop_front(), Some(1));
+    /// assert_eq!(cll.pop_front(), Some(2));
+    /// assert_eq!(cll.pop_front(), Some(3));
+    /// assert_eq!(cll.pop_front(), None);
+    /// ```
+    pub fn pop_front(&mut self) -> Option<T> {
+        let tail = self.tail?;
+        // SAFETY: `tail` is a node of this list.
+        Some(unsafe { self.unlink_after(tail) }.value)
+    }
+
+    /// Removes the tail element and returns it, or `None` if the list is empty.
+    ///
+    /// ## Expensive
+    /// Has to traverse entire list to find the node before the tail.
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.remove(self.len.checked_sub(1)?)
+    }
+
+    /// Removes the element at `index` and returns it,
+    /// or `None` if `index` is out of bounds.
+    ///
+    /// ```
+    /// # use garlic::raw_circular_linked_list::*;
+    /// let mut cll: CircularLinkedList<_> = "abcd".chars().collect();
+    /// assert_eq!(cll.remove(2), Some('c'));
+    /// assert_eq!(cll.remove(3), None);
+    /// assert_eq!(format!("{cll:?}"), "['a', 'b', 'd']");
+    /// ```
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        if index >= self.len {
+            return None;
+        }
+
+        let prev = match index {
+            0 => self.tail,
+            _ => self.node_at(index - 1),
+        }?;
+        // SAFETY: `prev` is a node of this list.
+        Some(unsafe { self.unlink_after(prev) }.value)
+    }
+
+    /// Keeps the first `len` elements and drops the rest.
+    ///
+    /// Does nothing if `len` is greater than or equal to the list's current length.
+    ///
+    /// ```
+    /// # use garlic::raw_circular_linked_list::*;
+    /// let mut cll: CircularLinkedList<_> = (1..=5).collect();
+    /// cll.truncate(2);
+    /// assert_eq!(format!("{cll:?}"), "[1, 2]");
+    /// assert_eq!(cll.iter().map_copied().take(4).collect::<Vec<_>>(), [1, 2, 1, 2]);
+    /// ```
+    pub fn truncate(&mut self, len: usize) {
+        if len == 0 {
+            return self.clear();
+        }
+
+        let Some(new_tail) = self.node_at(len - 1) else {
+            return;
+        };
+        while self.tail != Some(new_tail) {
+            // SAFETY: `new_tail` is a node of this list.
+            drop(unsafe { self.unlink_after(new_tail) });
+        }
+    }
+
+    /// Removes all elements.
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    /// Moves the head `n` nodes forward along the circle,
+    /// so the element at index `n` becomes the new head.
+    ///
+    /// No nodes are reallocated or relinked, only the tail pointer moves.
+    ///
+    /// ```
+    /// # use garlic::raw_circular_linked_list::*;
+    /// let mut cll: CircularLinkedList<_> = (1..=4).collect();
+    /// cll.rotate_forward(1);
+    /// assert_eq!(format!("{cll:?}"), "[2, 3, 4, 1]");
+    /// cll.rotate_backward(2);
+    /// assert_eq!(format!("{cll:?}"), "[4, 1, 2, 3]");
+    /// ```
+    pub fn rotate_forward(&mut self, n: usize) {
+        if self.len == 0 {
+            return;
+        }
+
+        for _ in 0..n % self.len {
+            self.tail = self.head();
+        }
+    }
+
+    /// Moves the head `n` nodes backward along the circle,
+    /// so the current tail becomes the head when `n` is `1`.
+    ///
+    /// ## Expensive
+    /// The list is singly linked, so this walks forward `len - n` nodes instead.
+    pub fn rotate_backward(&mut self, n: usize) {
+        if self.len == 0 {
+            return;
+        }
+
+        self.rotate_forward(self.len - n % self.len);
+    }
+
+    /// Rotates the list so that the first element matching `predicate`
+    /// becomes the head.
+    ///
+    /// Returns `false` and leaves the list untouched if no element matches.
+    pub fn rotate_to<F>(&mut self, predicate: F) -> bool
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let Some(n) = self.iter_values_once().position(predicate) else {
+            return false;
+        };
+        self.rotate_forward(n);
+        true
+    }
+
+    /// Whether `other` holds the same elements in the same cyclic order,
+    /// possibly starting from a different head.
+    ///
+    /// ```
+    /// # use garlic::raw_circular_linked_list::*;
+    /// let cll = CircularLinkedList::from([1, 2, 3]);
+    /// assert!(cll.eq_up_to_rotation(&CircularLinkedList::from([3, 1, 2])));
+    /// assert!(!cll.eq_up_to_rotation(&CircularLinkedList::from([3, 2, 1])));
+    /// ```
+    pub fn eq_up_to_rotation(&self, other: &Self) -> bool
+    where
+        T: PartialEq,
+    {
+        let a: Vec<_> = self.iter_values_once().collect();
+        let b: Vec<_> = other.iter_values_once().collect();
+        is_rotation(&a, b.iter())
+    }
+
+    /// Like [`eq_up_to_rotation`](Self::eq_up_to_rotation),
+    /// but also accepts `other` running around the circle in the opposite direction.
+    ///
+    /// ```
+    /// # use garlic::raw_circular_linked_list::*;
+    /// let cll = CircularLinkedList::from([1, 2, 3, 4]);
+    /// assert!(cll.eq_up_to_rotation_and_reflection(&CircularLinkedList::from([2, 1, 4, 3])));
+    /// assert!(!cll.eq_up_to_rotation_and_reflection(&CircularLinkedList::from([1, 3, 2, 4])));
+    /// ```
+    pub fn eq_up_to_rotation_and_reflection(&self, other: &Self) -> bool
+    where
+        T: PartialEq,
+    {
+        let a: Vec<_> = self.iter_values_once().collect();
+        let b: Vec<_> = other.iter_values_once().collect();
+        is_rotation(&a, b.iter()) || is_rotation(&a, b.iter().rev())
+    }
+
+    /// Rotates the list so that it starts at its lexicographically smallest rotation,
+    /// see [`CircularLinkedList::canonicalize`].
+    ///
+    /// [`CircularLinkedList::canonicalize`]: crate::circular_linked_list::CircularLinkedList::canonicalize
+    ///
+    /// ```
+    /// # use garlic::raw_circular_linked_list::*;
+    /// let mut cll = CircularLinkedList::from([3, 1, 2, 1, 1]);
+    /// cll.canonicalize();
+    /// assert_eq!(cll, CircularLinkedList::from([1, 1, 3, 1, 2]));
+    /// ```
+    pub fn canonicalize(&mut self)
+    where
+        T: Ord,
+    {
+        let values: Vec<_> = self.iter_values_once().collect();
+        let n = least_rotation(&values);
+        self.rotate_forward(n);
+    }
+
+    fn head(&self) -> Option<NonNull<Node<T>>> {
+        // SAFETY: `tail` is a node of this list.
+        self.tail.map(|tail| unsafe { (*tail.as_ptr()).next })
+    }
+
+    fn node_at(&self, index: usize) -> Option<NonNull<Node<T>>> {
+        if index >= self.len {
+            return None;
+        }
+
+        let mut node = self.head()?;
+        for _ in 0..index {
+            // SAFETY: `node` is a node of this list.
+            node = unsafe { (*node.as_ptr()).next };
+        }
+        Some(node)
+    }
+
+    /// Links a new node after the tail, creating the self-looping node in an empty list.
+    ///
+    /// Returns the new node, which is the new head. Leaves `tail` untouched unless the list was empty.
+    fn link_after_tail(&mut self, value: T) -> NonNull<Node<T>> {
+        self.len += 1;
+        match self.tail {
+            // SAFETY: `tail` is a node of this list.
+            Some(tail) => unsafe { Self::link_after(tail, value) },
+            None => {
+                let node = Box::new(Node {
+                    value,
+                    next: NonNull::dangling(),
+                });
+                let node = NonNull::from(Box::leak(node));
+                // SAFETY: `node` was just allocated.
+                unsafe { (*node.as_ptr()).next = node };
+                self.tail = Some(node);
+                node
+            }
+        }
+    }
+
+    /// Links a new node right after `prev` and returns it.
+    ///
+    /// # Safety
+    /// `prev` must be a node of a list.
+    unsafe fn link_after(prev: NonNull<Node<T>>, value: T) -> NonNull<Node<T>> {
+        unsafe {
+            let node = Box::new(Node {
+                value,
+                next: (*prev.as_ptr()).next,
+            });
+            let node = NonNull::from(Box::leak(node));
+            (*prev.as_ptr()).next = node;
+            node
+        }
+    }
+
+    /// Unlinks the node that follows `prev` and takes back ownership of it.
+    ///
+    /// # Safety
+    /// `prev` must be a node of this list.
+    unsafe fn unlink_after(&mut self, prev: NonNull<Node<T>>) -> Box<Node<T>> {
+        unsafe {
+            let node = (*prev.as_ptr()).next;
+            self.len -= 1;
+
+            if node == prev {
+                self.tail = None;
+            } else {
+                (*prev.as_ptr()).next = (*node.as_ptr()).next;
+                if self.tail == Some(node) {
+                    self.tail = Some(prev);
+                }
+            }
+            Box::from_raw(node.as_ptr())
+        }
+    }
+
+    /// Creates an iterator that, by default,
+    /// will never end, unless the list is empty.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            cursor: self.head(),
+            tail: self.tail,
+            stop: false,
+            len: self.len,
+            pos: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Creates an iterator that, by default,
+    /// will iterate throught the list and stop at the tail element.
+    pub fn iter_once(&self) -> Iter<'_, T> {
+        self.iter().once()
+    }
+
+    /// Creates an iterator over mutable references that stops at the tail element.
+    ///
+    /// There is no never ending version, since a value can't be mutably borrowed twice.
+    ///
+    /// ```
+    /// # use garlic::raw_circular_linked_list::*;
+    /// let mut cll: CircularLinkedList<_> = (1..=3).collect();
+    /// cll.iter_mut().for_each(|x| *x *= 10);
+    /// assert_eq!(format!("{cll:?}"), "[10, 20, 30]");
+    /// ```
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            cursor: self.head(),
+            remaining: self.len,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Creates an iterator over values that,
+    /// like [`iter`](Self::iter), will never end unless the list is empty.
+    ///
+    /// ```
+    /// # use garlic::raw_circular_linked_list::*;
+    /// let cll: CircularLinkedList<_> = ["a", "b"].map(String::from).into_iter().collect();
+    /// let lens: Vec<_> = cll.iter_values().map(|s| s.len()).take(3).collect();
+    /// assert_eq!(lens, [1, 1, 1]);
+    /// ```
+    pub fn iter_values(&self) -> Values<'_, T> {
+        Values { iter: self.iter() }
+    }
+
+    /// Creates an iterator over values that stops at the tail element.
+    ///
+    /// ```
+    /// # use garlic::raw_circular_linked_list::*;
+    /// let cll: CircularLinkedList<_> = ["Mike", "Hank"].map(String::from).into_iter().collect();
+    /// let joined = cll.iter_values_once().fold(String::new(), |acc, s| acc + s);
+    /// assert_eq!(joined, "MikeHank");
+    /// ```
+    pub fn iter_values_once(&self) -> Values<'_, T> {
+        Values {
+            iter: self.iter_once(),
+        }
+    }
+
+    /// Same as [`iter_mut`](Self::iter_mut).
+    ///
+    /// ```
+    /// # use garlic::raw_circular_linked_list::*;
+    /// let mut cll: CircularLinkedList<_> = ["Mike", "Hank"].map(String::from).into_iter().collect();
+    /// for name in cll.iter_values_mut() {
+    ///     name.push('!');
+    /// }
+    /// assert_eq!(format!("{cll:?}"), r#"["Mike!", "Hank!"]"#);
+    /// ```
+    pub fn iter_values_mut(&mut self) -> IterMut<'_, T> {
+        self.iter_mut()
+    }
+
+    /// Creates a cursor starting at the head, for editing the list in place.
+    ///
+    /// ```
+    /// # use garlic::raw_circular_linked_list::*;
+    /// let mut cll = CircularLinkedList::from([1, 2, 3]);
+    /// let mut cursor = cll.cursor_mut();
+    /// cursor.move_next();
+    /// *cursor.current().unwrap() *= 10;
+    /// assert_eq!(cursor.peek_next(), Some(&mut 3));
+    /// assert_eq!(cursor.remove_current(), Some(20));
+    /// assert_eq!(cll, CircularLinkedList::from([1, 3]));
+    /// ```
+    pub fn cursor_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            current: self.head(),
+            prev: self.tail,
+            index: 0,
+            list: self,
+        }
+    }
+}
+
+/// Unlinks and frees the nodes one at a time, so even very long lists can't overflow the stack.
+///
+/// ```
+/// # use garlic::raw_circular_linked_list::*;
+/// let n = if cfg!(miri) { 100 } else { 3_000_000 };
+/// let cll: CircularLinkedList<_> = (0..n).collect();
+/// drop(cll);
+/// ```
+impl<T> Drop for CircularLinkedList<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+/// Iterator over the nodes of a [`CircularLinkedList`].
+///
+/// Created by [`CircularLinkedList::iter`] and [`CircularLinkedList::iter_once`].
+pub struct Iter<'a, T> {
+    cursor: Option<NonNull<Node<T>>>,
+    tail: Option<NonNull<Node<T>>>,
+    stop: bool,
+    len: usize,
+    pos: usize,
+    _marker: PhantomData<&'a T>,
+}
+
+// SAFETY: `Iter` only hands out `&Node<T>`, which only exposes `&T`, like `std::slice::Iter`.
+unsafe impl<T: Sync> Send for Iter<'_, T> {}
+unsafe impl<T: Sync> Sync for Iter<'_, T> {}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Self { ..*self }
+    }
+}
+
+impl<'a, T> Iter<'a, T> {
+    pub fn once(mut self) -> Self {
+        self.stop = true;
+        self
+    }
+
+    /// Copies the value of each node.
+    ///
+    /// Equivelent to `cll.map(|x| x.value)`
+    pub fn map_copied(self) -> impl Iterator<Item = T> + 'a
+    where
+        T: Copy,
+    {
+        self.map(|x| x.value)
+    }
+
+    /// Clones the value of each node.
+    ///
+    /// Equivelent to `cll.map(|x| x.value.clone())`
+    pub fn map_cloned(self) -> impl Iterator<Item = T> + 'a
+    where
+        T: Clone,
+    {
+        self.map(|x| x.value.clone())
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a Node<T>;
+
+    fn next(&mut self) -> Option<&'a Node<T>> {
+        let node = self.cursor.take()?;
+
+        // SAFETY: the list is borrowed for `'a`, so its nodes are alive and not mutated.
+        unsafe {
+            if !self.stop || Some(node) != self.tail {
+                self.cursor = Some((*node.as_ptr()).next);
+                self.pos = (self.pos + 1) % self.len;
+            }
+
+            Some(&*node.as_ptr())
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match (self.cursor, self.stop) {
+            (None, _) => (0, Some(0)),
+            (Some(_), false) => (usize::MAX, None),
+            (Some(_), true) => (self.len - self.pos, Some(self.len - self.pos)),
+        }
+    }
+}
+
+/// Only meaningful after [`Iter::once`].
+/// Calling [`len`](ExactSizeIterator::len) on a never ending iterator panics.
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+/// Iterator over the values of a [`CircularLinkedList`].
+///
+/// Created by [`CircularLinkedList::iter_values`] and [`CircularLinkedList::iter_values_once`].
+#[derive(Clone)]
+pub struct Values<'a, T> {
+    iter: Iter<'a, T>,
+}
+
+impl<'a, T> Iterator for Values<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.iter.next().map(|node| &node.value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// Only meaningful for iterators created with [`CircularLinkedList::iter_values_once`].
+/// Calling [`len`](ExactSizeIterator::len) on a never ending iterator panics.
+impl<T> ExactSizeIterator for Values<'_, T> {}
+
+/// Iterator over mutable references to the values of a [`CircularLinkedList`].
+///
+/// Created by [`CircularLinkedList::iter_mut`].
+pub struct IterMut<'a, T> {
+    cursor: Option<NonNull<Node<T>>>,
+    remaining: usize,
+    _marker: PhantomData<&'a mut T>,
+}
+
+// SAFETY: `IterMut` only hands out `&mut T`, like `std::slice::IterMut`.
+unsafe impl<T: Send> Send for IterMut<'_, T> {}
+unsafe impl<T: Sync> Sync for IterMut<'_, T> {}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        let node = self.cursor.take()?;
+
+        // SAFETY: the list is mutably borrowed for `'a`,
+        // and every node is handed out at most once.
+        unsafe {
+            self.remaining -= 1;
+            if self.remaining > 0 {
+                self.cursor = Some((*node.as_ptr()).next);
+            }
+
+            Some(&mut (*node.as_ptr()).value)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+/// A cursor over a [`CircularLinkedList`] that can insert, remove and replace elements.
+///
+/// Since the list is circular, the cursor never runs off the end:
+/// moving past the tail brings it back to the head.
+///
+/// Created by [`CircularLinkedList::cursor_mut`].
+pub struct CursorMut<'a, T> {
+    list: &'a mut CircularLinkedList<T>,
+    current: Option<NonNull<Node<T>>>,
+    prev: Option<NonNull<Node<T>>>,
+    index: usize,
+}
+
+impl<T> CursorMut<'_, T> {
+    /// Index of the current element, counting from the head,
+    /// or `None` if the list is empty.
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    /// Moves to the next element, wrapping from the tail back to the head.
+    pub fn move_next(&mut self) {
+        let Some(current) = self.current else {
+            return;
+        };
+        // SAFETY: `current` is a node of the cursor's list.
+        self.current = Some(unsafe { (*current.as_ptr()).next });
+        self.prev = Some(current);
+        self.index = (self.index + 1) % self.list.len;
+    }
+
+    /// Borrows the current element, or returns `None` if the list is empty.
+    pub fn current(&mut self) -> Option<&mut T> {
+        let current = self.current?;
+        // SAFETY: `current` is a node of the list, which the cursor borrows mutably.
+        Some(unsafe { &mut (*current.as_ptr()).value })
+    }
+
+    /// Borrows the element after the current one, or returns `None` if the list is empty.
+    ///
+    /// In a single element list, this is the current element itself.
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let current = self.current?;
+        // SAFETY: `current` and its successor are nodes of the list,
+        // which the cursor borrows mutably.
+        Some(unsafe { &mut (*(*current.as_ptr()).next.as_ptr()).value })
+    }
+
+    /// Inserts an element after the current one.
+    ///
+    /// If the list is empty, the new element becomes the current one.
+    pub fn insert_after(&mut self, value: T) {
+        let Some(current) = self.current else {
+            return self.insert_into_empty(value);
+        };
+
+        // SAFETY: `current` is a node of the cursor's list.
+        let node = unsafe { CircularLinkedList::link_after(current, value) };
+        self.list.len += 1;
+        if self.list.tail == Some(current) {
+            self.list.tail = Some(node);
+        }
+    }
+
+    /// Inserts an element before the current one.
+    ///
+    /// If the current element is the head, the new element becomes the head.
+    /// If the list is empty, the new element becomes the current one.
+    ///
+    /// ```
+    /// # use garlic::raw_circular_linked_list::*;
+    /// let mut cll = CircularLinkedList::from([2, 4]);
+    /// let mut cursor = cll.cursor_mut();
+    /// cursor.insert_before(1);
+    /// cursor.move_next();
+    /// cursor.insert_before(3);
+    /// cursor.insert_after(5);
+    /// assert_eq!(cursor.index(), Some(3));
+    /// assert_eq!(cll, CircularLinkedList::from([1, 2, 3, 4, 5]));
+    /// ```
+    pub fn insert_before(&mut self, value: T) {
+        let Some(prev) = self.prev.filter(|_| self.current.is_some()) else {
+            return self.insert_into_empty(value);
+        };
+
+        // The head is `tail.next`, so a node inserted before the head becomes the head.
+        // SAFETY: `prev` is a node of the cursor's list.
+        let node = unsafe { CircularLinkedList::link_after(prev, value) };
+        self.list.len += 1;
+        self.prev = Some(node);
+        self.index += 1;
+    }
+
+    fn insert_into_empty(&mut self, value: T) {
+        self.list.push(value);
+        self.current = self.list.head();
+        self.prev = self.list.tail;
+        self.index = 0;
+    }
+
+    /// Removes the current element and returns it, moving the cursor to the next element.
+    ///
+    /// Returns `None` if the list is empty.
+    pub fn remove_current(&mut self) -> Option<T> {
+        self.current.take()?;
+        let prev = self.prev.take().unwrap();
+        // SAFETY: `prev` is a node of the cursor's list.
+        let node = unsafe { self.list.unlink_after(prev) };
+
+        if self.list.is_empty() {
+            self.index = 0;
+        } else {
+            // SAFETY: `prev` is still a node of the list.
+            self.current = Some(unsafe { (*prev.as_ptr()).next });
+            self.prev = Some(prev);
+            self.index %= self.list.len;
+        }
+        Some(node.value)
+    }
+
+    /// Replaces the current element, returning the old one.
+    ///
+    /// If the list is empty, `value` is inserted as the only element and `None` is returned.
+    pub fn replace_current(&mut self, value: T) -> Option<T> {
+        let Some(current) = self.current() else {
+            self.insert_into_empty(value);
+            return None;
+        };
+        Some(std::mem::replace(current, value))
+    }
+
+    /// Splits the list after the current element.
+    ///
+    /// The cursor's list keeps the elements from the head up to and including the current one,
+    /// everything after it, up to the tail, is returned as a new list.
+    ///
+    /// ```
+    /// # use garlic::raw_circular_linked_list::*;
+    /// let mut cll = CircularLinkedList::from([1, 2, 3, 4, 5]);
+    /// let mut cursor = cll.cursor_mut();
+    /// cursor.move_next();
+    /// let rest = cursor.split_after();
+    /// assert_eq!(cll, CircularLinkedList::from([1, 2]));
+    /// assert_eq!(rest, CircularLinkedList::from([3, 4, 5]));
+    /// ```
+    pub fn split_after(&mut self) -> CircularLinkedList<T> {
+        let mut rest = CircularLinkedList::new();
+        let (Some(current), Some(tail)) = (self.current, self.list.tail) else {
+            return rest;
+        };
+        if current == tail {
+            return rest;
+        }
+
+        // SAFETY: `current` and `tail` are distinct nodes of the cursor's list,
+        // the nodes after `current` move to `rest` as a circle of their own.
+        unsafe {
+            let head = (*tail.as_ptr()).next;
+            (*tail.as_ptr()).next = (*current.as_ptr()).next;
+            (*current.as_ptr()).next = head;
+        }
+
+        rest.tail = Some(tail);
+        rest.len = self.list.len - self.index - 1;
+        self.list.tail = Some(current);
+        self.list.len = self.index + 1;
+        rest
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for CircularLinkedList<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        f.debug_list().entries(self.iter_values_once()).finish()
+    }
+}
+
+impl<T: Clone> Clone for CircularLinkedList<T> {
+    fn clone(&self) -> Self {
+        self.iter_values_once().cloned().collect()
+    }
+}
+
+/// Lists are equal if they have the same elements in the same order, starting from the head.
+impl<T: PartialEq> PartialEq for CircularLinkedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter_values_once().eq(other.iter_values_once())
+    }
+}
+
+impl<T: Eq> Eq for CircularLinkedList<T> {}
+
+impl<T: Hash> Hash for CircularLinkedList<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len.hash(state);
+        for x in self.iter_values_once() {
+            x.hash(state);
+        }
+    }
+}
+
+/// Lexicographic comparison, starting from the head.
+impl<T: PartialOrd> PartialOrd for CircularLinkedList<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter_values_once()
+            .partial_cmp(other.iter_values_once())
+    }
+}
+
+impl<T: Ord> Ord for CircularLinkedList<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter_values_once().cmp(other.iter_values_once())
+    }
+}
+
+impl<T> std::iter::FromIterator<T> for CircularLinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut cll = Self::new();
+        cll.extend(iter);
+        cll
+    }
+}
+
+impl<T> Extend<T> for CircularLinkedList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.push(x);
+        }
+    }
+}
+
+impl<'a, T: Copy + 'a> Extend<&'a T> for CircularLinkedList<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<T> From<Vec<T>> for CircularLinkedList<T> {
+    fn from(vec: Vec<T>) -> Self {
+        vec.into_iter().collect()
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for CircularLinkedList<T> {
+    fn from(array: [T; N]) -> Self {
+        array.into_iter().collect()
+    }
+}
+
+impl<T> From<VecDeque<T>> for CircularLinkedList<T> {
+    fn from(deque: VecDeque<T>) -> Self {
+        deque.into_iter().collect()
+    }
+}
+
+impl<T> From<CircularLinkedList<T>> for Vec<T> {
+    fn from(cll: CircularLinkedList<T>) -> Self {
+        cll.into_iter().collect()
+    }
+}
+
+impl<T> From<CircularLinkedList<T>> for VecDeque<T> {
+    fn from(cll: CircularLinkedList<T>) -> Self {
+        cll.into_iter().collect()
+    }
+}
+
+impl<T> From<CircularLinkedList<T>> for LinkedList<T> {
+    fn from(cll: CircularLinkedList<T>) -> Self {
+        cll.into_iter().collect()
+    }
+}
+
+/// Consumes the list, yielding each element once, starting at the head.
+///
+/// ```
+/// # use garlic::raw_circular_linked_list::*;
+/// let cll = CircularLinkedList::from([String::from("Mike"), String::from("Hank")]);
+/// let mut names = vec![String::from("Walter")];
+/// names.extend(cll);
+/// assert_eq!(names, ["Walter", "Mike", "Hank"]);
+/// ```
+impl<T> IntoIterator for CircularLinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { list: self }
+    }
+}
+
+/// Iterates through the list once, like [`CircularLinkedList::iter_once`].
+impl<'a, T> IntoIterator for &'a CircularLinkedList<T> {
+    type Item = &'a Node<T>;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter_once()
+    }
+}
+
+/// Iterates through the list once, like [`CircularLinkedList::iter_mut`].
+impl<'a, T> IntoIterator for &'a mut CircularLinkedList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+/// Owning iterator over the elements of a [`CircularLinkedList`].
+///
+/// Created by [`CircularLinkedList::into_iter`].
+pub struct IntoIter<T> {
+    list: CircularLinkedList<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len, Some(self.list.len))
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+/// Small cases covering every path through the unsafe code, cheap enough to run under Miri.
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(n: usize) -> CircularLinkedList<String> {
+        (0..n).map(|i| i.to_string()).collect()
+    }
+
+    fn values(cll: &CircularLinkedList<String>) -> Vec<&str> {
+        cll.iter_values_once().map(String::as_str).collect()
+    }
+
+    #[test]
+    fn push_and_pop() {
+        let mut cll = list(3);
+        cll.push_front(String::from("a"));
+        cll.insert(2, String::from("b"));
+        assert_eq!(values(&cll), ["a", "0", "b", "1", "2"]);
+        assert_eq!(cll.pop_back().as_deref(), Some("2"));
+        assert_eq!(cll.pop_front().as_deref(), Some("a"));
+        assert_eq!(cll.remove(1).as_deref(), Some("b"));
+        assert_eq!(values(&cll), ["0", "1"]);
+        cll.truncate(1);
+        assert_eq!(values(&cll), ["0"]);
+        assert_eq!(cll.pop_back().as_deref(), Some("0"));
+        assert!(cll.pop_front().is_none());
+        cll.push(String::from("c"));
+        assert_eq!(values(&cll), ["c"]);
+    }
+
+    #[test]
+    fn iterate() {
+        let mut cll = list(3);
+        let ends: Vec<_> = cll.iter().take(4).map(|x| x.value.as_str()).collect();
+        assert_eq!(ends, ["0", "1", "2", "0"]);
+
+        let mut iter = cll.iter_once();
+        let first = iter.next().unwrap();
+        let rest: Vec<_> = iter.map(|x| x.value.as_str()).collect();
+        assert_eq!((first.value.as_str(), rest), ("0", vec!["1", "2"]));
+
+        let mut refs: Vec<_> = cll.iter_mut().collect();
+        refs.reverse();
+        for (i, x) in refs.into_iter().enumerate() {
+            x.push_str(&i.to_string());
+        }
+        assert_eq!(values(&cll), ["02", "11", "20"]);
+    }
+
+    #[test]
+    fn rotate() {
+        let mut cll = list(4);
+        cll.rotate_forward(5);
+        cll.rotate_backward(3);
+        assert_eq!(values(&cll), ["2", "3", "0", "1"]);
+        assert!(cll.rotate_to(|x| x == "0"));
+        cll.canonicalize();
+        assert!(cll.eq_up_to_rotation(&list(4)));
+    }
+
+    #[test]
+    fn cursor() {
+        let mut cll = list(3);
+        let mut cursor = cll.cursor_mut();
+        cursor.insert_before(String::from("a"));
+        cursor.move_next();
+        cursor.move_next();
+        cursor.insert_after(String::from("b"));
+        cursor.current().unwrap().push('!');
+        cursor.peek_next().unwrap().push('?');
+        assert_eq!(cursor.remove_current().as_deref(), Some("2!"));
+        assert!(cursor.split_after().is_empty());
+        cursor.move_next();
+        let rest = cursor.split_after();
+        assert_eq!(values(&cll), ["a"]);
+        assert_eq!(values(&rest), ["0", "1", "b?"]);
+
+        let mut cursor = cll.cursor_mut();
+        while cursor.remove_current().is_some() {}
+        cursor.replace_current(String::from("c"));
+        cursor.insert_before(String::from("d"));
+        assert_eq!(values(&cll), ["d", "c"]);
+    }
+
+    #[test]
+    fn convert_and_drop() {
+        let cll = list(3);
+        let copy = cll.clone();
+        assert_eq!(Vec::from(cll), ["0", "1", "2"]);
+
+        let mut iter = copy.into_iter();
+        assert_eq!(iter.next().as_deref(), Some("0"));
+        drop(iter);
+    }
+}
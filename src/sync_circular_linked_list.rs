@@ -0,0 +1,481 @@
+use std::sync::{
+    Arc, Mutex, MutexGuard, PoisonError,
+    atomic::{AtomicUsize, Ordering},
+};
+
+/// A circular singly linked list that can be shared between threads.
+///
+/// Every method takes `&self`, and there is no list-wide lock:
+/// every node guards its link and its element with a [`Mutex`] each.
+/// Pushing locks the link of the node it links after,
+/// removing locks the removed element and the links on both sides of it,
+/// and a [`SharedCursor`] only locks the link it follows and the element it hands out,
+/// so threads working on different parts of the circle don't wait for each other.
+///
+/// A sentinel node sits between the tail and the head, so there's always
+/// a node to link after, and links are always locked in order from the sentinel,
+/// which keeps threads locking two of them at once from deadlocking.
+///
+/// # Usage
+/// ```
+/// # use garlic::sync_circular_linked_list::*;
+/// let players: SyncCircularLinkedList<_> = ["Mike", "Hank", "Gus"].into_iter().collect();
+/// let turn = players.cursor();
+///
+/// std::thread::scope(|s| {
+///     for _ in 0..3 {
+///         s.spawn(|| turn.advance());
+///     }
+/// });
+/// assert_eq!(turn.advance(), Some("Mike"));
+/// ```
+pub struct SyncCircularLinkedList<T> {
+    /// Follows the tail and precedes the head, holds no element.
+    sentinel: Arc<Node<T>>,
+    /// A node at or shortly before the tail, where [`push`](Self::push) starts looking for it.
+    tail_hint: Mutex<Arc<Node<T>>>,
+    len: AtomicUsize,
+}
+
+struct Node<T> {
+    /// `None` for the sentinel, and once the node has been removed from the circle.
+    value: Mutex<Option<T>>,
+    next: Mutex<Link<T>>,
+}
+
+struct Link<T> {
+    /// Only `None` while the list is being dropped.
+    node: Option<Arc<Node<T>>>,
+    /// Set when the node is unlinked. A removed node keeps pointing into the circle,
+    /// so cursors resting on it can move on, but nothing is linked after it anymore.
+    removed: bool,
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    // The links are always consistent when user code runs, so a poisoned lock is still usable.
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+impl<T> Node<T> {
+    fn new(value: Option<T>, next: Option<Arc<Node<T>>>) -> Arc<Self> {
+        Arc::new(Self {
+            value: Mutex::new(value),
+            next: Mutex::new(Link {
+                node: next,
+                removed: false,
+            }),
+        })
+    }
+
+    fn next(&self) -> Arc<Node<T>> {
+        lock(&self.next).node.clone().unwrap()
+    }
+}
+
+impl<T> Default for SyncCircularLinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> SyncCircularLinkedList<T> {
+    pub fn new() -> Self {
+        let sentinel = Node::new(None, None);
+        lock(&sentinel.next).node = Some(sentinel.clone());
+        Self {
+            tail_hint: Mutex::new(sentinel.clone()),
+            sentinel,
+            len: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::Relaxed)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.is_sentinel(&self.sentinel.next())
+    }
+
+    fn is_sentinel(&self, node: &Arc<Node<T>>) -> bool {
+        Arc::ptr_eq(node, &self.sentinel)
+    }
+
+    /// Pushes an element after the tail.
+    ///
+    /// Runs in constant time, unless the node it last pushed has been removed since.
+    ///
+    /// ```
+    /// # use garlic::sync_circular_linked_list::*;
+    /// let list = SyncCircularLinkedList::new();
+    /// std::thread::scope(|s| {
+    ///     for t in 0..8 {
+    ///         let list = &list;
+    ///         s.spawn(move || (0..1000).for_each(|i| list.push(t * 1000 + i)));
+    ///     }
+    /// });
+    ///
+    /// let mut values = list.to_vec();
+    /// values.sort();
+    /// assert_eq!(values, (0..8000).collect::<Vec<_>>());
+    /// ```
+    pub fn push(&self, value: T) {
+        let mut prev = lock(&self.tail_hint).clone();
+        loop {
+            let mut link = lock(&prev.next);
+            let next = link.node.clone().unwrap();
+            if !link.removed && self.is_sentinel(&next) {
+                let node = Node::new(Some(value), Some(next));
+                link.node = Some(node.clone());
+                self.len.fetch_add(1, Ordering::Relaxed);
+                drop(link);
+                *lock(&self.tail_hint) = node;
+                return;
+            }
+            drop(link);
+            prev = next;
+        }
+    }
+
+    /// Pushes an element in front of the head, making it the new head.
+    pub fn push_front(&self, value: T) {
+        let mut link = lock(&self.sentinel.next);
+        let node = Node::new(Some(value), link.node.take());
+        link.node = Some(node);
+        self.len.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Removes the head element and returns it, or `None` if the list is empty.
+    ///
+    /// Waits for any [`SharedCursor::advance_with`] working on the head to finish.
+    ///
+    /// ```
+    /// # use garlic::sync_circular_linked_list::*;
+    /// let list: SyncCircularLinkedList<_> = (0..10_000).collect();
+    /// let popped = std::sync::Mutex::new(Vec::new());
+    /// std::thread::scope(|s| {
+    ///     for _ in 0..4 {
+    ///         s.spawn(|| {
+    ///             while let Some(x) = list.pop_front() {
+    ///                 popped.lock().unwrap().push(x);
+    ///             }
+    ///         });
+    ///     }
+    /// });
+    ///
+    /// let mut popped = popped.into_inner().unwrap();
+    /// popped.sort();
+    /// assert_eq!(popped, (0..10_000).collect::<Vec<_>>());
+    /// assert!(list.is_empty());
+    /// ```
+    pub fn pop_front(&self) -> Option<T> {
+        loop {
+            let node = self.sentinel.next();
+            if self.is_sentinel(&node) {
+                return None;
+            }
+            let mut value = lock(&node.value);
+            if self.unlink(&self.sentinel, &node) {
+                return value.take();
+            }
+        }
+    }
+
+    /// Removes the element at `index` and returns it,
+    /// or `None` if `index` is out of bounds.
+    ///
+    /// While other threads push and remove, the index counts the elements
+    /// as they are when the walk from the head reaches them.
+    pub fn remove(&self, index: usize) -> Option<T> {
+        loop {
+            let mut prev = self.sentinel.clone();
+            let mut node = prev.next();
+            for _ in 0..index {
+                if self.is_sentinel(&node) {
+                    return None;
+                }
+                prev = node;
+                node = prev.next();
+            }
+            if self.is_sentinel(&node) {
+                return None;
+            }
+
+            // If `prev` or `node` was removed meanwhile, count again.
+            let mut value = lock(&node.value);
+            if self.unlink(&prev, &node) {
+                return value.take();
+            }
+        }
+    }
+
+    /// Removes the first element matching `predicate` and returns it.
+    ///
+    /// `predicate` may see an element more than once if the list changes
+    /// right when a match is being removed.
+    ///
+    /// ```
+    /// # use garlic::sync_circular_linked_list::*;
+    /// let list: SyncCircularLinkedList<_> = ["Mike", "Hank", "Gus"].into_iter().collect();
+    /// assert_eq!(list.remove_first(|name| name.starts_with('H')), Some("Hank"));
+    /// assert_eq!(list.remove_first(|name| name.is_empty()), None);
+    /// assert_eq!(list.to_vec(), ["Mike", "Gus"]);
+    /// ```
+    pub fn remove_first<F>(&self, mut predicate: F) -> Option<T>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut prev = self.sentinel.clone();
+        loop {
+            let node = prev.next();
+            if self.is_sentinel(&node) {
+                return None;
+            }
+
+            let mut value = lock(&node.value);
+            if value.as_ref().is_some_and(&mut predicate) {
+                if self.unlink(&prev, &node) {
+                    return value.take();
+                }
+                // `prev` or `node` was removed meanwhile, start over.
+                drop(value);
+                prev = self.sentinel.clone();
+                continue;
+            }
+            drop(value);
+            prev = node;
+        }
+    }
+
+    /// Copies every element into a [`Vec`], starting at the head.
+    ///
+    /// Elements pushed or removed by other threads meanwhile may or may not be included.
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        let mut values = Vec::with_capacity(self.len());
+        self.for_each(|x| values.push(x.clone()));
+        values
+    }
+
+    /// Calls `f` with every element, starting at the head, locking one at a time.
+    fn for_each(&self, mut f: impl FnMut(&T)) {
+        let mut node = self.sentinel.next();
+        while !self.is_sentinel(&node) {
+            if let Some(value) = &*lock(&node.value) {
+                f(value);
+            }
+            node = node.next();
+        }
+    }
+
+    /// Creates a cursor that can be shared between threads,
+    /// each [`advance`](SharedCursor::advance) handing out the next element around the circle.
+    pub fn cursor(&self) -> SharedCursor<'_, T> {
+        SharedCursor {
+            list: self,
+            current: Mutex::new(self.sentinel.clone()),
+        }
+    }
+
+    /// Unlinks `node` if it still follows `prev`, returning whether it did.
+    ///
+    /// Fails if either of them has been removed meanwhile.
+    fn unlink(&self, prev: &Arc<Node<T>>, node: &Arc<Node<T>>) -> bool {
+        let mut prev_link = lock(&prev.next);
+        if prev_link.removed || !Arc::ptr_eq(prev_link.node.as_ref().unwrap(), node) {
+            return false;
+        }
+
+        let mut link = lock(&node.next);
+        prev_link.node.clone_from(&link.node);
+        link.removed = true;
+        self.len.fetch_sub(1, Ordering::Relaxed);
+        true
+    }
+}
+
+/// Breaks the circle at the sentinel, the nodes then drop one at a time.
+///
+/// ```
+/// # use garlic::sync_circular_linked_list::*;
+/// let list: SyncCircularLinkedList<_> = (0..3_000_000).collect();
+/// drop(list);
+/// ```
+impl<T> Drop for SyncCircularLinkedList<T> {
+    fn drop(&mut self) {
+        lock(&self.sentinel.next).node = None;
+    }
+}
+
+/// Unlinks the nodes after this one one at a time, so even very long chains,
+/// like the one a cursor resting on a removed node keeps alive, can't overflow the stack.
+///
+/// ```
+/// # use garlic::sync_circular_linked_list::*;
+/// let list: SyncCircularLinkedList<_> = (0..3_000_000).collect();
+/// let cursor = list.cursor();
+/// cursor.advance();
+/// while list.pop_front().is_some() {}
+/// drop(cursor);
+/// ```
+impl<T> Drop for Node<T> {
+    fn drop(&mut self) {
+        let link = self.next.get_mut().unwrap_or_else(PoisonError::into_inner);
+        let mut next = link.node.take();
+        while let Some(node) = next {
+            next = Arc::into_inner(node).and_then(|mut node| {
+                let link = node.next.get_mut().unwrap_or_else(PoisonError::into_inner);
+                link.node.take()
+            });
+        }
+    }
+}
+
+/// A cursor over a [`SyncCircularLinkedList`] that any number of threads can advance together.
+///
+/// Every call to [`advance`](Self::advance) moves the cursor one element further
+/// and hands that element to the calling thread, so threads sharing a cursor
+/// take turns around the circle without ever getting the same turn.
+/// Elements removed while the cursor rests on them are skipped.
+///
+/// Created by [`SyncCircularLinkedList::cursor`].
+///
+/// ```
+/// # use garlic::sync_circular_linked_list::*;
+/// let list: SyncCircularLinkedList<_> = (0..10).collect();
+/// let cursor = list.cursor();
+/// let turns = std::sync::Mutex::new(vec![0; 10]);
+///
+/// std::thread::scope(|s| {
+///     for _ in 0..4 {
+///         s.spawn(|| {
+///             for _ in 0..1000 {
+///                 let x = cursor.advance().unwrap();
+///                 turns.lock().unwrap()[x] += 1;
+///             }
+///         });
+///     }
+/// });
+///
+/// // Every element got exactly the same number of turns.
+/// assert_eq!(turns.into_inner().unwrap(), [400; 10]);
+/// ```
+///
+/// Pushing and removing elements while other threads advance is fine too.
+/// ```
+/// # use garlic::sync_circular_linked_list::*;
+/// let list: SyncCircularLinkedList<_> = (0..100).collect();
+/// let cursor = list.cursor();
+///
+/// std::thread::scope(|s| {
+///     s.spawn(|| {
+///         for i in 100..1100 {
+///             list.push(i);
+///             list.pop_front();
+///         }
+///     });
+///     s.spawn(|| {
+///         for i in 0..1000 {
+///             list.push_front(i);
+///             assert_eq!(list.remove_first(|&x| x == i), Some(i));
+///         }
+///     });
+///     for _ in 0..3 {
+///         s.spawn(|| {
+///             for _ in 0..1000 {
+///                 assert!(cursor.advance().is_some());
+///             }
+///         });
+///     }
+/// });
+///
+/// assert_eq!(list.to_vec(), (1000..1100).collect::<Vec<_>>());
+/// assert_eq!(list.len(), 100);
+/// ```
+pub struct SharedCursor<'a, T> {
+    list: &'a SyncCircularLinkedList<T>,
+    /// The node handed out last, or the sentinel before the first advance.
+    current: Mutex<Arc<Node<T>>>,
+}
+
+impl<T> SharedCursor<'_, T> {
+    /// Moves to the next element and calls `f` with it,
+    /// or returns `None` if the list is empty.
+    ///
+    /// The element stays locked while `f` runs,
+    /// and everything else that needs it waits for `f` to finish.
+    /// So `f` deadlocks if it, or another thread it waits for,
+    /// - removes the element, e.g. with [`pop_front`] while it's the head,
+    /// - calls [`remove_first`], [`to_vec`] or formats the list with `Debug`,
+    ///   which look at every element,
+    /// - or advances any cursor onto the element, which always happens in a list of one.
+    ///
+    /// Pushing, and removing other elements by position, is fine.
+    ///
+    /// [`pop_front`]: SyncCircularLinkedList::pop_front
+    /// [`remove_first`]: SyncCircularLinkedList::remove_first
+    /// [`to_vec`]: SyncCircularLinkedList::to_vec
+    ///
+    /// ```
+    /// # use garlic::sync_circular_linked_list::*;
+    /// let list: SyncCircularLinkedList<_> = (1..=3).collect();
+    /// let cursor = list.cursor();
+    /// cursor.advance();
+    /// // The head is 1, not the element `f` was given.
+    /// assert_eq!(cursor.advance_with(|x| (*x, list.pop_front())), Some((2, Some(1))));
+    /// cursor.advance_with(|&mut x| list.push(x * 10));
+    /// assert_eq!(list.to_vec(), [2, 3, 30]);
+    /// ```
+    pub fn advance_with<R, F>(&self, f: F) -> Option<R>
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        loop {
+            let node = {
+                let mut current = lock(&self.current);
+                let next = current.next();
+                if self.list.is_sentinel(&next) && self.list.is_empty() {
+                    return None;
+                }
+                *current = next.clone();
+                next
+            };
+
+            // Skips the sentinel and removed elements.
+            if let Some(value) = lock(&node.value).as_mut() {
+                return Some(f(value));
+            }
+        }
+    }
+
+    /// Moves to the next element and returns a copy of it,
+    /// or returns `None` if the list is empty.
+    pub fn advance(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.advance_with(|x| x.clone())
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for SyncCircularLinkedList<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        let mut l = f.debug_list();
+        self.for_each(|x| {
+            l.entry(x);
+        });
+        l.finish()
+    }
+}
+
+impl<T> std::iter::FromIterator<T> for SyncCircularLinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let list = Self::new();
+        for x in iter {
+            list.push(x);
+        }
+        list
+    }
+}